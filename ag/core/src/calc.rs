@@ -1,6 +1,6 @@
 use core::{convert::Infallible, ops::RangeInclusive};
 
-use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios};
 use sanctum_svc_lido_core::calc::{LidoCalc, LidoCalcErr};
 use sanctum_svc_marinade_core::calc::{MarinadeCalc, MarinadeCalcErr};
 use sanctum_svc_spl_core::calc::{SplCalc, SplCalcErr};
@@ -76,6 +76,33 @@ impl SvcCalcAgRef<'_> {
             Self::Wsol(c) => c.svc_sol_to_lst(lamports_amount),
         })
     }
+
+    #[inline]
+    pub const fn svc_lst_to_sol_ratios(&self) -> Result<LstToSolRatios, SvcCalcAgErr> {
+        Ok(match self {
+            Self::Lido(c) => match c.svc_lst_to_sol_ratios() {
+                Err(e) => return Err(SvcCalcAgErr::Lido(e)),
+                Ok(r) => r,
+            },
+            Self::Marinade(c) => match c.svc_lst_to_sol_ratios() {
+                Err(e) => return Err(SvcCalcAgErr::Marinade(e)),
+                Ok(r) => r,
+            },
+            Self::SanctumSpl(c) => match c.svc_lst_to_sol_ratios() {
+                Err(e) => return Err(SvcCalcAgErr::SanctumSpl(e)),
+                Ok(r) => r,
+            },
+            Self::SanctumSplMulti(c) => match c.svc_lst_to_sol_ratios() {
+                Err(e) => return Err(SvcCalcAgErr::SanctumSplMulti(e)),
+                Ok(r) => r,
+            },
+            Self::Spl(c) => match c.svc_lst_to_sol_ratios() {
+                Err(e) => return Err(SvcCalcAgErr::Spl(e)),
+                Ok(r) => r,
+            },
+            Self::Wsol(c) => c.svc_lst_to_sol_ratios(),
+        })
+    }
 }
 
 impl SolValCalc for SvcCalcAgRef<'_> {
@@ -105,3 +132,21 @@ impl SolValCalc for SvcCalcAg {
         self.as_ref_const().svc_sol_to_lst(lamports_amount)
     }
 }
+
+impl SolValCalcRatios for SvcCalcAgRef<'_> {
+    type Error = SvcCalcAgErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.svc_lst_to_sol_ratios()
+    }
+}
+
+impl SolValCalcRatios for SvcCalcAg {
+    type Error = SvcCalcAgErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.as_ref_const().svc_lst_to_sol_ratios()
+    }
+}
@@ -1,4 +1,7 @@
-use sanctum_svc_core::traits::SolValCalcAccs;
+use sanctum_svc_core::{
+    instructions::svc_ix::{SvcIx, SvcIxTy},
+    traits::SolValCalcAccs,
+};
 use sanctum_svc_lido_core::instructions::sol_val_calc::LidoCalcAccs;
 use sanctum_svc_marinade_core::instructions::sol_val_calc::MarinadeCalcAccs;
 use sanctum_svc_spl_core::instructions::sol_val_calc::{
@@ -62,7 +65,18 @@ pub type SvcCalcAccsAgAccFlags = SvcAg<
     WsolAccFlags,
 >;
 
+pub type SvcIxAg = SvcIx<SvcCalcAccsAgKeysOwned, SvcCalcAccsAgAccFlags>;
+
 impl SvcCalcAccsAgRef<'_> {
+    /// Complete SolValCalc interface instruction for `lst_mint`.
+    ///
+    /// `amt` is LST amount for [`SvcIxTy::LstToSol`]
+    /// and lamport amount for [`SvcIxTy::SolToLst`].
+    #[inline]
+    pub fn svc_ix(&self, lst_mint: [u8; 32], ty: SvcIxTy, amt: u64) -> SvcIxAg {
+        SvcIx::new(*self.svc_program_id(), lst_mint, self, ty, amt)
+    }
+
     #[inline]
     pub const fn svc_suf_keys_owned(&self) -> SvcCalcAccsAgKeysOwned {
         match self {
@@ -212,4 +212,25 @@ impl SvcAgTy {
             _ => return None,
         })
     }
+
+    /// Number of accounts in the accounts suffix
+    /// of this calculator program's SolValCalc instructions
+    #[inline]
+    pub const fn suf_len(&self) -> usize {
+        match self {
+            Self::Lido(_)
+            | Self::Marinade(_)
+            | Self::SanctumSpl(_)
+            | Self::SanctumSplMulti(_)
+            | Self::Spl(_) => sanctum_svc_generic::instructions::IX_SUF_ACCS_LEN,
+            Self::Wsol(_) => sanctum_svc_wsol_core::instructions::sol_val_calc::IX_SUF_ACCS_LEN,
+        }
+    }
+
+    /// Total number of accounts, prefix + suffix,
+    /// of this calculator program's SolValCalc instructions
+    #[inline]
+    pub const fn ix_accs_len(&self) -> usize {
+        sanctum_svc_core::instructions::IX_PRE_ACCS_LEN + self.suf_len()
+    }
 }
@@ -0,0 +1,128 @@
+use std::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use sanctum_svc_ag_core::{
+    sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios},
+    sanctum_svc_generic::upgrade::PoolProgUpgradedErr,
+};
+
+use crate::SvcAgStd;
+
+// Re-exports
+pub use sanctum_svc_ag_core::calc::*;
+
+impl SvcAgStd {
+    /// [`Self::as_sol_val_calc`], but errors if
+    /// - the calc has not been initialized
+    /// - upgrade detection is enabled and the stake pool program has been upgraded
+    #[inline]
+    pub const fn try_as_sol_val_calc(&self) -> Result<SvcCalcAgRef<'_>, SvcAgStdCalcErr> {
+        if let Err(e) = self.upgrade_check().check() {
+            return Err(SvcAgStdCalcErr::PoolProgUpgraded(e));
+        }
+        match self.as_sol_val_calc() {
+            Some(c) => Ok(c),
+            None => Err(SvcAgStdCalcErr::Uninit),
+        }
+    }
+
+    /// [`Self::try_as_sol_val_calc`], but also errors if
+    /// `lst_mint` is not the LST mint this calc quotes for
+    #[inline]
+    pub fn try_as_sol_val_calc_for_mint(
+        &self,
+        lst_mint: &[u8; 32],
+    ) -> Result<SvcCalcAgRef<'_>, SvcAgStdCalcErr> {
+        let calc = self.try_as_sol_val_calc()?;
+        match self.lst_mint() {
+            Some(expected) if expected == lst_mint => Ok(calc),
+            Some(expected) => Err(SvcAgStdCalcErr::LstMintMismatch {
+                expected: *expected,
+                actual: *lst_mint,
+            }),
+            None => Err(SvcAgStdCalcErr::Uninit),
+        }
+    }
+
+    /// [`SolValCalc::lst_to_sol`], but errors if
+    /// `lst_mint` is not the LST mint this calc quotes for
+    #[inline]
+    pub fn lst_to_sol_checked(
+        &self,
+        lst_mint: &[u8; 32],
+        lst_amount: u64,
+    ) -> Result<RangeInclusive<u64>, SvcAgStdCalcErr> {
+        self.try_as_sol_val_calc_for_mint(lst_mint)?
+            .svc_lst_to_sol(lst_amount)
+            .map_err(SvcAgStdCalcErr::Calc)
+    }
+
+    /// [`SolValCalc::sol_to_lst`], but errors if
+    /// `lst_mint` is not the LST mint this calc quotes for
+    #[inline]
+    pub fn sol_to_lst_checked(
+        &self,
+        lst_mint: &[u8; 32],
+        lamports_amount: u64,
+    ) -> Result<RangeInclusive<u64>, SvcAgStdCalcErr> {
+        self.try_as_sol_val_calc_for_mint(lst_mint)?
+            .svc_sol_to_lst(lamports_amount)
+            .map_err(SvcAgStdCalcErr::Calc)
+    }
+}
+
+impl SolValCalc for SvcAgStd {
+    type Error = SvcAgStdCalcErr;
+
+    #[inline]
+    fn lst_to_sol(&self, lst_amount: u64) -> Result<RangeInclusive<u64>, Self::Error> {
+        self.try_as_sol_val_calc()?
+            .svc_lst_to_sol(lst_amount)
+            .map_err(SvcAgStdCalcErr::Calc)
+    }
+
+    #[inline]
+    fn sol_to_lst(&self, lamports_amount: u64) -> Result<RangeInclusive<u64>, Self::Error> {
+        self.try_as_sol_val_calc()?
+            .svc_sol_to_lst(lamports_amount)
+            .map_err(SvcAgStdCalcErr::Calc)
+    }
+}
+
+impl SolValCalcRatios for SvcAgStd {
+    type Error = SvcAgStdCalcErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.try_as_sol_val_calc()?
+            .svc_lst_to_sol_ratios()
+            .map_err(SvcAgStdCalcErr::Calc)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcAgStdCalcErr {
+    /// Accounts required to create the calc have not been fetched yet
+    Uninit,
+    PoolProgUpgraded(PoolProgUpgradedErr),
+    Calc(SvcCalcAgErr),
+
+    /// Quoted for an LST mint that is not the one this calc is for
+    LstMintMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl Display for SvcAgStdCalcErr {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uninit => f.write_str("calc not yet initialized"),
+            Self::PoolProgUpgraded(e) => Display::fmt(e, f),
+            Self::Calc(e) => Display::fmt(e, f),
+            Self::LstMintMismatch { .. } => f.write_str("lst mint mismatch"),
+        }
+    }
+}
+
+impl Error for SvcAgStdCalcErr {}
@@ -1,4 +1,9 @@
-use sanctum_svc_ag_core::{calc::SvcCalcAgRef, instructions::SvcCalcAccsAgRef};
+use sanctum_svc_ag_core::{
+    calc::SvcCalcAgRef,
+    instructions::{SvcCalcAccsAgRef, SvcIxAg},
+    sanctum_svc_core::instructions::svc_ix::SvcIxTy,
+    sanctum_svc_generic::upgrade::UpgradeCheck,
+};
 
 use sanctum_svc_lido_std::LidoSvcStd;
 use sanctum_svc_marinade_std::MarinadeSvcStd;
@@ -12,6 +17,13 @@ pub use sanctum_svc_marinade_std;
 pub use sanctum_svc_spl_std;
 pub use sanctum_svc_wsol_std;
 
+pub mod calc;
+pub mod registry;
+pub mod resolve;
+
+#[cfg(feature = "return-data")]
+pub mod return_data;
+
 pub mod update;
 
 // simple newtype to workaround orphan rules
@@ -50,6 +62,73 @@ impl SvcAgStd {
             SvcAg::Wsol(_) => SvcAg::Wsol(WsolSvcStd),
         })
     }
+
+    /// Opt-in to detecting stake pool program upgrades.
+    ///
+    /// This adds the calculator program state and stake pool program data accounts
+    /// to the accounts to update with.
+    ///
+    /// No-op for calculators that do not have a stake pool program (wSOL).
+    #[inline]
+    pub const fn with_upgrade_check(self) -> Self {
+        Self(match self.0 {
+            SvcAg::Lido(c) => SvcAg::Lido(c.with_upgrade_check()),
+            SvcAg::Marinade(c) => SvcAg::Marinade(c.with_upgrade_check()),
+            SvcAg::SanctumSpl(c) => SvcAg::SanctumSpl(c.with_upgrade_check()),
+            SvcAg::SanctumSplMulti(c) => SvcAg::SanctumSplMulti(c.with_upgrade_check()),
+            SvcAg::Spl(c) => SvcAg::Spl(c.with_upgrade_check()),
+            SvcAg::Wsol(c) => SvcAg::Wsol(c),
+        })
+    }
+
+    /// Bind SPL-family calculators to `lst_mint` so that updating with
+    /// a stake pool that has a different `pool_mint` fails.
+    ///
+    /// No-op for calculators with a fixed LST mint (Lido, Marinade, wSOL).
+    /// Lido and Marinade updates always check the fetched pool state against it,
+    /// wSOL has no pool state to check.
+    #[inline]
+    pub const fn with_lst_mint(self, lst_mint: [u8; 32]) -> Self {
+        Self(match self.0 {
+            SvcAg::SanctumSpl(c) => SvcAg::SanctumSpl(c.with_lst_mint(lst_mint)),
+            SvcAg::SanctumSplMulti(c) => SvcAg::SanctumSplMulti(c.with_lst_mint(lst_mint)),
+            SvcAg::Spl(c) => SvcAg::Spl(c.with_lst_mint(lst_mint)),
+            c @ (SvcAg::Lido(_) | SvcAg::Marinade(_) | SvcAg::Wsol(_)) => c,
+        })
+    }
+}
+
+/// Accessors
+impl SvcAgStd {
+    /// Always [`UpgradeCheck::Disabled`] for calculators that do not have
+    /// a stake pool program (wSOL)
+    #[inline]
+    pub const fn upgrade_check(&self) -> UpgradeCheck {
+        match &self.0 {
+            SvcAg::Lido(c) => c.upgrade_check,
+            SvcAg::Marinade(c) => c.upgrade_check,
+            SvcAg::SanctumSpl(c) => c.upgrade_check,
+            SvcAg::SanctumSplMulti(c) => c.upgrade_check,
+            SvcAg::Spl(c) => c.upgrade_check,
+            SvcAg::Wsol(_) => UpgradeCheck::Disabled,
+        }
+    }
+
+    /// The LST mint this calculator quotes for.
+    ///
+    /// `None` for SPL-family calculators that have not been
+    /// bound to a mint or updated yet.
+    #[inline]
+    pub const fn lst_mint(&self) -> Option<&[u8; 32]> {
+        match &self.0 {
+            SvcAg::Lido(c) => Some(c.lst_mint()),
+            SvcAg::Marinade(c) => Some(c.lst_mint()),
+            SvcAg::SanctumSpl(c) => c.lst_mint(),
+            SvcAg::SanctumSplMulti(c) => c.lst_mint(),
+            SvcAg::Spl(c) => c.lst_mint(),
+            SvcAg::Wsol(c) => Some(c.lst_mint()),
+        }
+    }
 }
 
 /// SolValCalc traits
@@ -93,3 +172,22 @@ impl SvcAgStd {
         }
     }
 }
+
+/// Instructions
+impl SvcAgStd {
+    /// Complete SolValCalc interface instruction for [`Self::lst_mint`].
+    ///
+    /// `amt` is LST amount for [`SvcIxTy::LstToSol`]
+    /// and lamport amount for [`SvcIxTy::SolToLst`].
+    ///
+    /// `None` for SPL-family calculators that have not been
+    /// bound to a mint or updated yet.
+    ///
+    /// Convert to a `solana_instruction::Instruction`
+    /// with the `solana-instruction` feature enabled.
+    #[inline]
+    pub fn svc_ix(&self, ty: SvcIxTy, amt: u64) -> Option<SvcIxAg> {
+        let lst_mint = *self.lst_mint()?;
+        Some(self.as_sol_val_calc_accs().svc_ix(lst_mint, ty, amt))
+    }
+}
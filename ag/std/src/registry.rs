@@ -0,0 +1,148 @@
+use std::{collections::HashMap, error::Error, fmt::Display, ops::RangeInclusive};
+
+use sanctum_svc_std::driver::accounts_to_update_all;
+
+use crate::{
+    calc::SvcAgStdCalcErr,
+    update::{UpdateErr, UpdateMap, UpdateSvc, UpdateSvcErr},
+    SvcAgStd,
+};
+
+/// Type alias just to be explicit about what this pubkey is supposed to be
+pub type LstMint = [u8; 32];
+
+/// Many [`SvcAgStd`]s keyed by LST mint.
+///
+/// Entries added through this type's methods are bound to their key's mint,
+/// so updating SPL-family entries with a stake pool of a different mint fails,
+/// and quoting entries for a different mint fails.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct SvcAgRegistry(pub HashMap<LstMint, SvcAgStd>);
+
+/// Constructors
+impl SvcAgRegistry {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Accessors
+impl SvcAgRegistry {
+    #[inline]
+    pub fn get(&self, lst_mint: &LstMint) -> Option<&SvcAgStd> {
+        self.0.get(lst_mint)
+    }
+}
+
+/// Mutators
+impl SvcAgRegistry {
+    /// Binds `svc` to `lst_mint` with [`SvcAgStd::with_lst_mint`].
+    ///
+    /// Returns the replaced entry, if any
+    #[inline]
+    pub fn insert(&mut self, lst_mint: LstMint, svc: SvcAgStd) -> Option<SvcAgStd> {
+        self.0.insert(lst_mint, svc.with_lst_mint(lst_mint))
+    }
+
+    #[inline]
+    pub fn remove(&mut self, lst_mint: &LstMint) -> Option<SvcAgStd> {
+        self.0.remove(lst_mint)
+    }
+}
+
+impl FromIterator<(LstMint, SvcAgStd)> for SvcAgRegistry {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (LstMint, SvcAgStd)>>(iter: T) -> Self {
+        Self(HashMap::from_iter(
+            iter.into_iter()
+                .map(|(mint, svc)| (mint, svc.with_lst_mint(mint))),
+        ))
+    }
+}
+
+impl Extend<(LstMint, SvcAgStd)> for SvcAgRegistry {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (LstMint, SvcAgStd)>>(&mut self, iter: T) {
+        self.0.extend(
+            iter.into_iter()
+                .map(|(mint, svc)| (mint, svc.with_lst_mint(mint))),
+        )
+    }
+}
+
+/// Update
+impl SvcAgRegistry {
+    /// Deduped union of accounts required to update all entries
+    #[inline]
+    pub fn accounts_to_update(&self) -> Vec<[u8; 32]> {
+        accounts_to_update_all(self.0.values())
+    }
+
+    /// Updates all entries, continuing even if some of them fail.
+    ///
+    /// Returns the errors of entries that failed to update, keyed by LST mint.
+    /// Failed entries retain their previous state.
+    pub fn update_all(
+        &mut self,
+        update_map: impl UpdateMap,
+    ) -> HashMap<LstMint, UpdateErr<UpdateSvcErr>> {
+        self.0
+            .iter_mut()
+            .filter_map(|(mint, svc)| svc.update_svc(&update_map).err().map(|e| (*mint, e)))
+            .collect()
+    }
+}
+
+/// SolValCalc
+impl SvcAgRegistry {
+    #[inline]
+    pub fn lst_to_sol(
+        &self,
+        lst_mint: &LstMint,
+        lst_amount: u64,
+    ) -> Result<RangeInclusive<u64>, SvcAgRegistryErr> {
+        self.try_get(lst_mint)?
+            .lst_to_sol_checked(lst_mint, lst_amount)
+            .map_err(SvcAgRegistryErr::Calc)
+    }
+
+    #[inline]
+    pub fn sol_to_lst(
+        &self,
+        lst_mint: &LstMint,
+        lamports_amount: u64,
+    ) -> Result<RangeInclusive<u64>, SvcAgRegistryErr> {
+        self.try_get(lst_mint)?
+            .sol_to_lst_checked(lst_mint, lamports_amount)
+            .map_err(SvcAgRegistryErr::Calc)
+    }
+
+    #[inline]
+    fn try_get(&self, lst_mint: &LstMint) -> Result<&SvcAgStd, SvcAgRegistryErr> {
+        self.get(lst_mint)
+            .ok_or(SvcAgRegistryErr::UnknownMint { mint: *lst_mint })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcAgRegistryErr {
+    /// No entry for this LST mint
+    UnknownMint {
+        mint: LstMint,
+    },
+    Calc(SvcAgStdCalcErr),
+}
+
+impl Display for SvcAgRegistryErr {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownMint { .. } => f.write_str("UnknownMint"),
+            Self::Calc(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for SvcAgRegistryErr {}
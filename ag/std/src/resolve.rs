@@ -0,0 +1,106 @@
+//! Resolving an LST mint to its [`SvcCalcStdInitData`]
+
+use std::{error::Error, fmt::Display};
+
+use sanctum_svc_ag_core::{
+    sanctum_svc_lido_core, sanctum_svc_marinade_core,
+    sanctum_svc_spl_core::{
+        keys::{sanctum_spl, sanctum_spl_multi, spl},
+        sanctum_spl_stake_pool_core::StakePool,
+    },
+    sanctum_svc_wsol_core, SvcAg,
+};
+
+use crate::{
+    update::{AccountOwner, UpdateMap},
+    SvcAgStd, SvcCalcStdInitData,
+};
+
+/// Resolves the calculator type and init data for `lst_mint`.
+///
+/// Lido, Marinade and wSOL are resolved by their known LST mints.
+///
+/// All other LSTs are assumed to be SPL-family stake pools. In that case,
+/// `candidate_pools` are the addresses of stake pool accounts to scan in `update_map`.
+/// The first candidate with a matching `pool_mint` owned by a supported stake pool program
+/// is returned, with the calculator type determined by the owner program.
+/// Candidates missing from `update_map` or that are not stake pools are skipped.
+///
+/// `update_map`'s accounts must know their owner program, [`AccountOwner`],
+/// to tell apart stake pools of the different stake pool programs.
+pub fn resolve_init_data<'m, M: UpdateMap>(
+    lst_mint: &[u8; 32],
+    candidate_pools: impl IntoIterator<Item = [u8; 32]>,
+    update_map: &'m M,
+) -> Result<SvcCalcStdInitData, ResolveErr>
+where
+    M::Account<'m>: AccountOwner,
+{
+    match *lst_mint {
+        sanctum_svc_lido_core::keys::LST_MINT => return Ok(SvcAg::Lido(())),
+        sanctum_svc_marinade_core::keys::LST_MINT => return Ok(SvcAg::Marinade(())),
+        sanctum_svc_wsol_core::LST_MINT => return Ok(SvcAg::Wsol(())),
+        _ => (),
+    }
+    let mut unknown_owner = None;
+    let found = candidate_pools.into_iter().find_map(|pool_addr| {
+        let acc = update_map.get_account(&pool_addr)?;
+        let pool = StakePool::borsh_de(acc.data()).ok()?;
+        if pool.pool_mint != *lst_mint {
+            return None;
+        }
+        match acc.owner() {
+            spl::POOL_PROG_ID => Some(SvcAg::Spl(pool_addr)),
+            sanctum_spl::POOL_PROG_ID => Some(SvcAg::SanctumSpl(pool_addr)),
+            sanctum_spl_multi::POOL_PROG_ID => Some(SvcAg::SanctumSplMulti(pool_addr)),
+            _ => {
+                unknown_owner.get_or_insert(pool_addr);
+                None
+            }
+        }
+    });
+    found.ok_or(match unknown_owner {
+        Some(pool) => ResolveErr::UnknownPoolOwner { pool },
+        None => ResolveErr::NotFound,
+    })
+}
+
+/// Constructors
+impl SvcAgStd {
+    /// [`Self::new`] with init data from [`resolve_init_data`]
+    #[inline]
+    pub fn resolve<'m, M: UpdateMap>(
+        lst_mint: &[u8; 32],
+        candidate_pools: impl IntoIterator<Item = [u8; 32]>,
+        update_map: &'m M,
+    ) -> Result<Self, ResolveErr>
+    where
+        M::Account<'m>: AccountOwner,
+    {
+        resolve_init_data(lst_mint, candidate_pools, update_map).map(Self::new)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolveErr {
+    /// No candidate stake pool with a matching `pool_mint`
+    NotFound,
+
+    /// No candidate stake pool with a matching `pool_mint`
+    /// is owned by a supported stake pool program.
+    /// `pool` is the first candidate with a matching `pool_mint`,
+    /// whose owner is not a supported stake pool program.
+    UnknownPoolOwner { pool: [u8; 32] },
+}
+
+impl Display for ResolveErr {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("NotFound"),
+            Self::UnknownPoolOwner { .. } => f.write_str("UnknownPoolOwner"),
+        }
+    }
+}
+
+impl Error for ResolveErr {}
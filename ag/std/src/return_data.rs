@@ -0,0 +1,281 @@
+//! Parsing SolValCalc return data out of simulated transactions.
+//!
+//! Return data is reported by RPC as a base58 program ID and base64 data,
+//! either in `returnData` of the `simulateTransaction` response for the last
+//! instruction that set it, or in a `Program return: <program ID> <data>`
+//! log line for every instruction that set it.
+//!
+//! The runtime strips trailing zero bytes from return data, e.g. the top bytes
+//! of a little-endian `max` below 2^56, so shorter data is zero-padded before decoding.
+
+use std::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sanctum_svc_ag_core::{
+    sanctum_svc_core::instructions::return_data::{
+        decode_return_data, ReturnDataErr, RETURN_DATA_LEN,
+    },
+    SvcAgTy,
+};
+
+const PROGRAM_RETURN_LOG_PREFIX: &str = "Program return: ";
+
+/// A quote returned by a SolValCalc program
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SvcQuote {
+    /// The calculator program that returned this quote
+    pub ty: SvcAgTy,
+
+    /// `(min, max)` of the output range.
+    /// This is SOL value for `LstToSol` and LST amount for `SolToLst`.
+    pub range: RangeInclusive<u64>,
+}
+
+/// `data` shorter than [`RETURN_DATA_LEN`] is zero-padded up to it
+/// to undo the runtime's stripping of trailing zero bytes.
+///
+/// Errors if `program_id` is not a known SolValCalc program
+/// or if `data` is not valid SolValCalc return data,
+/// including if it is longer than [`RETURN_DATA_LEN`]
+#[inline]
+pub fn decode_svc_return_data(
+    program_id: &[u8; 32],
+    data: &[u8],
+) -> Result<SvcQuote, SvcReturnDataErr> {
+    let ty =
+        SvcAgTy::try_from_svc_program_id(program_id).ok_or(SvcReturnDataErr::UnknownProgram {
+            program_id: *program_id,
+        })?;
+    let mut padded = [0u8; RETURN_DATA_LEN];
+    padded
+        .get_mut(..data.len())
+        .ok_or(SvcReturnDataErr::ReturnData(ReturnDataErr::InvalidLen(
+            data.len(),
+        )))?
+        .copy_from_slice(data);
+    let range = decode_return_data(&padded).map_err(SvcReturnDataErr::ReturnData)?;
+    Ok(SvcQuote { ty, range })
+}
+
+/// [`decode_svc_return_data`] for a base58 `program_id` and base64 `data`,
+/// as reported by RPC
+#[inline]
+pub fn decode_svc_return_data_encoded(
+    program_id: &str,
+    data: &str,
+) -> Result<SvcQuote, SvcReturnDataErr> {
+    let program_id = decode_program_id(program_id)?;
+    let data = STANDARD
+        .decode(data)
+        .map_err(|_| SvcReturnDataErr::Base64)?;
+    decode_svc_return_data(&program_id, &data)
+}
+
+/// Decodes every SolValCalc quote in `return_datas`,
+/// each a base58 program ID and base64 data pair as reported by RPC.
+///
+/// Return data set by programs that are not SolValCalc programs are skipped.
+pub fn svc_quotes<'a>(
+    return_datas: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> impl Iterator<Item = Result<SvcQuote, SvcReturnDataErr>> {
+    return_datas.into_iter().filter_map(|(program_id, data)| {
+        match decode_svc_return_data_encoded(program_id, data) {
+            Err(SvcReturnDataErr::UnknownProgram { .. }) => None,
+            res => Some(res),
+        }
+    })
+}
+
+/// [`svc_quotes`] for the `Program return: ` lines of a simulated transaction's logs,
+/// which includes return data set by inner instructions, in order of execution.
+///
+/// All other log lines are skipped.
+pub fn svc_quotes_from_logs<'a>(
+    logs: impl IntoIterator<Item = &'a str>,
+) -> impl Iterator<Item = Result<SvcQuote, SvcReturnDataErr>> {
+    svc_quotes(
+        logs.into_iter()
+            .filter_map(|log| log.strip_prefix(PROGRAM_RETURN_LOG_PREFIX)?.split_once(' ')),
+    )
+}
+
+fn decode_program_id(program_id: &str) -> Result<[u8; 32], SvcReturnDataErr> {
+    let mut pk = [0u8; 32];
+    match bs58::decode(program_id).onto(&mut pk) {
+        Ok(32) => Ok(pk),
+        _ => Err(SvcReturnDataErr::Base58),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcReturnDataErr {
+    /// Program ID was not valid base58 or not 32 bytes long
+    Base58,
+
+    /// Return data was not valid base64
+    Base64,
+
+    /// Return data was set by a program that is not a known SolValCalc program
+    UnknownProgram {
+        program_id: [u8; 32],
+    },
+
+    ReturnData(ReturnDataErr),
+}
+
+impl Display for SvcReturnDataErr {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base58 => f.write_str("invalid base58 program id"),
+            Self::Base64 => f.write_str("invalid base64 return data"),
+            Self::UnknownProgram { .. } => f.write_str("UnknownProgram"),
+            Self::ReturnData(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for SvcReturnDataErr {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ReturnData(e) => Some(e),
+            Self::Base58 | Self::Base64 | Self::UnknownProgram { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sanctum_svc_ag_core::{
+        sanctum_svc_core::instructions::return_data::encode_return_data, sanctum_svc_lido_core,
+        sanctum_svc_wsol_core, SvcAg,
+    };
+
+    use super::*;
+
+    fn b58(pk: &[u8; 32]) -> String {
+        bs58::encode(pk).into_string()
+    }
+
+    /// As reported by RPC, with trailing zero bytes stripped
+    fn rpc_b64(range: &RangeInclusive<u64>) -> String {
+        let data = encode_return_data(range).unwrap();
+        let end = data.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+        STANDARD.encode(&data[..end])
+    }
+
+    #[test]
+    fn truncated_return_data() {
+        let range = 1..=1_000;
+        let data = rpc_b64(&range);
+        assert_eq!(STANDARD.decode(&data).unwrap().len(), 10);
+        assert_eq!(
+            decode_svc_return_data_encoded(&b58(&sanctum_svc_lido_core::ID), &data),
+            Ok(SvcQuote {
+                ty: SvcAg::Lido(()),
+                range
+            })
+        );
+    }
+
+    #[test]
+    fn all_zero_return_data() {
+        let data = rpc_b64(&(0..=0));
+        assert!(data.is_empty());
+        assert_eq!(
+            decode_svc_return_data_encoded(&b58(&sanctum_svc_wsol_core::ID), &data),
+            Ok(SvcQuote {
+                ty: SvcAg::Wsol(()),
+                range: 0..=0
+            })
+        );
+    }
+
+    #[test]
+    fn full_len_return_data() {
+        let range = u64::MAX..=u64::MAX;
+        assert_eq!(
+            decode_svc_return_data(
+                &sanctum_svc_lido_core::ID,
+                &encode_return_data(&range).unwrap()
+            ),
+            Ok(SvcQuote {
+                ty: SvcAg::Lido(()),
+                range
+            })
+        );
+    }
+
+    #[test]
+    fn too_long_return_data() {
+        assert_eq!(
+            decode_svc_return_data(&sanctum_svc_lido_core::ID, &[1; RETURN_DATA_LEN + 1]),
+            Err(SvcReturnDataErr::ReturnData(ReturnDataErr::InvalidLen(
+                RETURN_DATA_LEN + 1
+            )))
+        );
+    }
+
+    #[test]
+    fn program_return_log_line() {
+        let range = 5..=7;
+        let line = format!(
+            "{PROGRAM_RETURN_LOG_PREFIX}{} {}",
+            b58(&sanctum_svc_lido_core::ID),
+            rpc_b64(&range)
+        );
+        let logs = [
+            "Program log: Instruction: LstToSol",
+            line.as_str(),
+            "Program 1idUSy4MGGKyKhvjSnGZ6Zc7Q4eKQcibym4BkEEw9KR success",
+        ];
+        assert_eq!(
+            svc_quotes_from_logs(logs).collect::<Vec<_>>(),
+            [Ok(SvcQuote {
+                ty: SvcAg::Lido(()),
+                range
+            })]
+        );
+    }
+
+    #[test]
+    fn skips_unknown_program() {
+        let unknown = b58(&[1; 32]);
+        let lido = b58(&sanctum_svc_lido_core::ID);
+        let data = rpc_b64(&(1..=2));
+        assert_eq!(
+            svc_quotes([
+                (unknown.as_str(), data.as_str()),
+                (lido.as_str(), data.as_str())
+            ])
+            .collect::<Vec<_>>(),
+            [Ok(SvcQuote {
+                ty: SvcAg::Lido(()),
+                range: 1..=2
+            })]
+        );
+    }
+
+    #[test]
+    fn bad_base58() {
+        // 0, O, I, l are not in the base58 alphabet
+        assert_eq!(
+            decode_svc_return_data_encoded("0OIl", ""),
+            Err(SvcReturnDataErr::Base58)
+        );
+        // valid base58, but not 32 bytes
+        assert_eq!(
+            decode_svc_return_data_encoded("11", ""),
+            Err(SvcReturnDataErr::Base58)
+        );
+    }
+
+    #[test]
+    fn bad_base64() {
+        assert_eq!(
+            decode_svc_return_data_encoded(&b58(&sanctum_svc_lido_core::ID), "!!!"),
+            Err(SvcReturnDataErr::Base64)
+        );
+    }
+}
@@ -1,11 +1,20 @@
-use core::{iter::Chain, slice};
+use core::{error::Error, fmt::Display, iter::Chain, slice};
 
 use generic_array_struct::generic_array_struct;
 
-use crate::{instructions::internal_utils::caba, traits::SolValCalcAccs};
+use crate::{
+    instructions::{
+        internal_utils::caba,
+        lst_to_sol::{LstToSolIxData, LST_TO_SOL_IX_DISCM},
+        sol_to_lst::{SolToLstIxData, SOL_TO_LST_IX_DISCM},
+    },
+    traits::SolValCalcAccs,
+};
 
 pub mod lst_to_sol;
+pub mod return_data;
 pub mod sol_to_lst;
+pub mod svc_ix;
 
 mod internal_utils;
 
@@ -74,10 +83,107 @@ impl<const DISCM: u8> IxData<DISCM> {
     pub const fn as_buf(&self) -> &[u8; IX_DATA_LEN] {
         &self.0
     }
+
+    /// This is LST amount for `LstToSol` and lamport amount for `SolToLst`
+    #[inline]
+    pub const fn amt(&self) -> u64 {
+        let [_discm, amt @ ..] = self.0;
+        u64::from_le_bytes(amt)
+    }
+
+    /// Errors if `data` is not exactly [`IX_DATA_LEN`] long
+    /// or if its discriminant is not `DISCM`
+    #[inline]
+    pub const fn try_from_buf(data: &[u8]) -> Result<Self, IxDataErr> {
+        let buf = match ix_data_buf(data) {
+            Ok(b) => b,
+            Err(e) => return Err(e),
+        };
+        if buf[0] != DISCM {
+            return Err(IxDataErr::UnknownDiscm(buf[0]));
+        }
+        Ok(Self(*buf))
+    }
+}
+
+/// Decoded data of either of the 2 SolValCalc interface instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcIxData {
+    LstToSol(LstToSolIxData),
+    SolToLst(SolToLstIxData),
+}
+
+impl SvcIxData {
+    #[inline]
+    pub const fn try_from_buf(data: &[u8]) -> Result<Self, IxDataErr> {
+        let buf = match ix_data_buf(data) {
+            Ok(b) => b,
+            Err(e) => return Err(e),
+        };
+        Ok(match buf[0] {
+            LST_TO_SOL_IX_DISCM => Self::LstToSol(IxData(*buf)),
+            SOL_TO_LST_IX_DISCM => Self::SolToLst(IxData(*buf)),
+            discm => return Err(IxDataErr::UnknownDiscm(discm)),
+        })
+    }
+
+    #[inline]
+    pub const fn as_buf(&self) -> &[u8; IX_DATA_LEN] {
+        match self {
+            Self::LstToSol(d) => d.as_buf(),
+            Self::SolToLst(d) => d.as_buf(),
+        }
+    }
+
+    #[inline]
+    pub const fn discm(&self) -> u8 {
+        self.as_buf()[0]
+    }
+
+    /// This is LST amount for `LstToSol` and lamport amount for `SolToLst`
+    #[inline]
+    pub const fn amt(&self) -> u64 {
+        match self {
+            Self::LstToSol(d) => d.amt(),
+            Self::SolToLst(d) => d.amt(),
+        }
+    }
 }
 
+#[inline]
+const fn ix_data_buf(data: &[u8]) -> Result<&[u8; IX_DATA_LEN], IxDataErr> {
+    match data.first_chunk() {
+        Some(buf) if data.len() == IX_DATA_LEN => Ok(buf),
+        _ => Err(IxDataErr::InvalidLen(data.len())),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IxDataErr {
+    /// Instruction data was not exactly [`IX_DATA_LEN`] bytes long.
+    /// Contains the actual length.
+    InvalidLen(usize),
+
+    /// First byte of instruction data was not a known discriminant.
+    /// Contains the actual discriminant.
+    UnknownDiscm(u8),
+}
+
+impl Display for IxDataErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLen(len) => write!(f, "invalid ix data len {len}"),
+            Self::UnknownDiscm(discm) => write!(f, "unknown ix discriminant {discm}"),
+        }
+    }
+}
+
+impl Error for IxDataErr {}
+
 // Genericized Input
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct IxAccs<T, P> {
     /// Interface account prefix; [`IxPreAccs`]
     pub ix_prefix: IxPreAccs<T>,
@@ -135,3 +241,65 @@ pub fn svc_ix_is_writer<T, S: SolValCalcAccs>(
         suf: suf.suf_is_writer(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMTS: [u64; 4] = [0, 1, 1_000_000_000, u64::MAX];
+
+    #[test]
+    fn ix_data_round_trip() {
+        AMTS.into_iter().for_each(|amt| {
+            let lts = LstToSolIxData::new(amt);
+            let stl = SolToLstIxData::new(amt);
+            assert_eq!(LstToSolIxData::try_from_buf(lts.as_buf()), Ok(lts));
+            assert_eq!(SolToLstIxData::try_from_buf(stl.as_buf()), Ok(stl));
+            assert_eq!(
+                SvcIxData::try_from_buf(lts.as_buf()),
+                Ok(SvcIxData::LstToSol(lts))
+            );
+            assert_eq!(
+                SvcIxData::try_from_buf(stl.as_buf()),
+                Ok(SvcIxData::SolToLst(stl))
+            );
+            [lts.amt(), stl.amt()]
+                .into_iter()
+                .for_each(|a| assert_eq!(a, amt));
+        });
+    }
+
+    #[test]
+    fn ix_data_buf_invalid_len() {
+        let long = [0u8; IX_DATA_LEN + 1];
+        [&long[..0], &long[..IX_DATA_LEN - 1], &long[..]]
+            .into_iter()
+            .for_each(|data| {
+                let expected = IxDataErr::InvalidLen(data.len());
+                assert_eq!(ix_data_buf(data), Err(expected));
+                assert_eq!(LstToSolIxData::try_from_buf(data), Err(expected));
+                assert_eq!(SolToLstIxData::try_from_buf(data), Err(expected));
+                assert_eq!(SvcIxData::try_from_buf(data), Err(expected));
+            });
+    }
+
+    #[test]
+    fn ix_data_unknown_discm() {
+        let mut data = *LstToSolIxData::new(1).as_buf();
+        data[0] = 2;
+        assert_eq!(
+            SvcIxData::try_from_buf(&data),
+            Err(IxDataErr::UnknownDiscm(2))
+        );
+
+        // each IxData only accepts its own discriminant
+        assert_eq!(
+            LstToSolIxData::try_from_buf(SolToLstIxData::new(1).as_buf()),
+            Err(IxDataErr::UnknownDiscm(SOL_TO_LST_IX_DISCM))
+        );
+        assert_eq!(
+            SolToLstIxData::try_from_buf(LstToSolIxData::new(1).as_buf()),
+            Err(IxDataErr::UnknownDiscm(LST_TO_SOL_IX_DISCM))
+        );
+    }
+}
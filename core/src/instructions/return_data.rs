@@ -0,0 +1,70 @@
+//! Return data of both the `LstToSol` and `SolToLst` instructions:
+//! `(min, max)` of the output range, each encoded as a little-endian u64
+
+use core::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use super::internal_utils::caba;
+
+pub const RETURN_DATA_LEN: usize = 16;
+
+/// Errors if `range.start() > range.end()`
+#[inline]
+pub const fn encode_return_data(
+    range: &RangeInclusive<u64>,
+) -> Result<[u8; RETURN_DATA_LEN], ReturnDataErr> {
+    const A: usize = RETURN_DATA_LEN;
+
+    let (min, max) = (*range.start(), *range.end());
+    if min > max {
+        return Err(ReturnDataErr::MinGtMax);
+    }
+
+    let mut d = [0u8; A];
+    d = caba::<A, 0, 8>(d, &min.to_le_bytes());
+    d = caba::<A, 8, 8>(d, &max.to_le_bytes());
+
+    Ok(d)
+}
+
+/// Errors if `data` is not exactly [`RETURN_DATA_LEN`] long
+/// or if the decoded `min > max`
+#[inline]
+pub const fn decode_return_data(data: &[u8]) -> Result<RangeInclusive<u64>, ReturnDataErr> {
+    let len_err = ReturnDataErr::InvalidLen(data.len());
+    if data.len() != RETURN_DATA_LEN {
+        return Err(len_err);
+    }
+    let (min, max) = match data.split_first_chunk::<8>() {
+        Some((min, rem)) => match rem.first_chunk::<8>() {
+            Some(max) => (u64::from_le_bytes(*min), u64::from_le_bytes(*max)),
+            None => return Err(len_err),
+        },
+        None => return Err(len_err),
+    };
+    if min > max {
+        return Err(ReturnDataErr::MinGtMax);
+    }
+    Ok(min..=max)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnDataErr {
+    /// Return data was not exactly [`RETURN_DATA_LEN`] bytes long.
+    /// Contains the actual length.
+    InvalidLen(usize),
+
+    /// Range start was greater than range end
+    MinGtMax,
+}
+
+impl Display for ReturnDataErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLen(len) => write!(f, "invalid return data len {len}"),
+            Self::MinGtMax => f.write_str("return data min > max"),
+        }
+    }
+}
+
+impl Error for ReturnDataErr {}
@@ -0,0 +1,131 @@
+use crate::{
+    instructions::{
+        lst_to_sol::LstToSolIxData, sol_to_lst::SolToLstIxData, IxAccs, IxPreAccs, SvcIxData,
+        IX_PRE_IS_SIGNER, IX_PRE_IS_WRITER,
+    },
+    traits::SolValCalcAccs,
+};
+
+/// Which of the 2 SolValCalc interface instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcIxTy {
+    LstToSol,
+    SolToLst,
+}
+
+impl SvcIxData {
+    /// # Args
+    /// - `amt`. This is LST amount for `LstToSol` and lamport amount for `SolToLst`
+    #[inline]
+    pub const fn new(ty: SvcIxTy, amt: u64) -> Self {
+        match ty {
+            SvcIxTy::LstToSol => Self::LstToSol(LstToSolIxData::new(amt)),
+            SvcIxTy::SolToLst => Self::SolToLst(SolToLstIxData::new(amt)),
+        }
+    }
+
+    #[inline]
+    pub const fn ty(&self) -> SvcIxTy {
+        match self {
+            Self::LstToSol(_) => SvcIxTy::LstToSol,
+            Self::SolToLst(_) => SvcIxTy::SolToLst,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// A complete SolValCalc interface instruction.
+///
+/// `K` and `F` are the calculator program's account suffix keys and flags types,
+/// [`SolValCalcAccs::KeysOwned`] and [`SolValCalcAccs::AccFlags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SvcIx<K, F> {
+    pub program_id: [u8; 32],
+    pub keys: IxAccs<[u8; 32], K>,
+    pub is_signer: IxAccs<bool, F>,
+    pub is_writer: IxAccs<bool, F>,
+    pub data: SvcIxData,
+}
+
+/// Constructors
+impl<K, F> SvcIx<K, F> {
+    /// # Args
+    /// - `program_id` the calculator program's program ID
+    /// - `amt`. This is LST amount for `LstToSol` and lamport amount for `SolToLst`
+    #[inline]
+    pub fn new<S: SolValCalcAccs<KeysOwned = K, AccFlags = F>>(
+        program_id: [u8; 32],
+        lst_mint: [u8; 32],
+        accs: &S,
+        ty: SvcIxTy,
+        amt: u64,
+    ) -> Self {
+        Self {
+            program_id,
+            keys: IxAccs::new(IxPreAccs::memset(lst_mint), accs.suf_keys_owned()),
+            is_signer: IxAccs::new(IX_PRE_IS_SIGNER, accs.suf_is_signer()),
+            is_writer: IxAccs::new(IX_PRE_IS_WRITER, accs.suf_is_writer()),
+            data: SvcIxData::new(ty, amt),
+        }
+    }
+}
+
+/// Accessors
+impl<K: AsRef<[[u8; 32]]>, F: AsRef<[bool]>> SvcIx<K, F> {
+    /// Accounts of this instruction, in order
+    #[inline]
+    pub fn account_metas(&self) -> impl Iterator<Item = AccountMeta> + '_ {
+        self.keys
+            .seq()
+            .zip(self.is_signer.seq())
+            .zip(self.is_writer.seq())
+            .map(|((pubkey, is_signer), is_writable)| AccountMeta {
+                pubkey: *pubkey,
+                is_signer: *is_signer,
+                is_writable: *is_writable,
+            })
+    }
+}
+
+#[cfg(feature = "solana-instruction")]
+mod solana_instruction_conv {
+    use alloc::vec::Vec;
+
+    use solana_pubkey::Pubkey;
+
+    use super::{AccountMeta, SvcIx};
+
+    impl From<AccountMeta> for solana_instruction::AccountMeta {
+        #[inline]
+        fn from(
+            AccountMeta {
+                pubkey,
+                is_signer,
+                is_writable,
+            }: AccountMeta,
+        ) -> Self {
+            Self {
+                pubkey: Pubkey::new_from_array(pubkey),
+                is_signer,
+                is_writable,
+            }
+        }
+    }
+
+    impl<K: AsRef<[[u8; 32]]>, F: AsRef<[bool]>> From<SvcIx<K, F>> for solana_instruction::Instruction {
+        #[inline]
+        fn from(ix: SvcIx<K, F>) -> Self {
+            Self {
+                program_id: Pubkey::new_from_array(ix.program_id),
+                accounts: ix.account_metas().map(Into::into).collect::<Vec<_>>(),
+                data: ix.data.as_buf().to_vec(),
+            }
+        }
+    }
+}
@@ -1,4 +1,16 @@
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "solana-instruction")]
+extern crate alloc;
+
+// Re-exports
+pub use sanctum_u64_ratio;
+#[cfg(feature = "update")]
+pub use sanctum_update_traits;
+
 pub mod instructions;
+pub mod lst_to_lst;
 pub mod traits;
+
+#[cfg(feature = "update")]
+pub mod update;
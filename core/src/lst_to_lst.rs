@@ -0,0 +1,90 @@
+use core::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use crate::traits::SolValCalc;
+
+/// Values one LST in terms of another by composing 2 [`SolValCalc`]s,
+/// going through SOL.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LstToLstCalc<I, O> {
+    /// Calculator of the LST to convert from
+    pub inp: I,
+
+    /// Calculator of the LST to convert to
+    pub out: O,
+}
+
+/// Constructors
+impl<I, O> LstToLstCalc<I, O> {
+    #[inline]
+    pub const fn new(inp: I, out: O) -> Self {
+        Self { inp, out }
+    }
+}
+
+impl<I: SolValCalc, O: SolValCalc> LstToLstCalc<I, O> {
+    /// Returns the range of `out` LST amounts that `inp_amount` of the `inp` LST is worth:
+    /// `inp.lst_to_sol()` followed by `out.sol_to_lst()` on both ends of the intermediate range.
+    #[inline]
+    pub fn lst_to_lst(
+        &self,
+        inp_amount: u64,
+    ) -> Result<RangeInclusive<u64>, LstToLstCalcErr<I::Error, O::Error>> {
+        let sol = self
+            .inp
+            .lst_to_sol(inp_amount)
+            .map_err(LstToLstCalcErr::Inp)?;
+        widened(&sol, |lamports| self.out.sol_to_lst(lamports)).map_err(LstToLstCalcErr::Out)
+    }
+
+    /// Inverse of [`Self::lst_to_lst`]:
+    /// returns the range of `inp` LST amounts that `out_amount` of the `out` LST is worth.
+    /// `out.lst_to_sol()` followed by `inp.sol_to_lst()` on both ends of the intermediate range.
+    #[inline]
+    pub fn lst_to_lst_rev(
+        &self,
+        out_amount: u64,
+    ) -> Result<RangeInclusive<u64>, LstToLstCalcErr<I::Error, O::Error>> {
+        let sol = self
+            .out
+            .lst_to_sol(out_amount)
+            .map_err(LstToLstCalcErr::Out)?;
+        widened(&sol, |lamports| self.inp.sol_to_lst(lamports)).map_err(LstToLstCalcErr::Inp)
+    }
+}
+
+/// Applies `f` to both ends of `range` and returns the smallest range
+/// that contains both outputs
+#[inline]
+fn widened<E>(
+    range: &RangeInclusive<u64>,
+    f: impl Fn(u64) -> Result<RangeInclusive<u64>, E>,
+) -> Result<RangeInclusive<u64>, E> {
+    let lo = f(*range.start())?;
+    if range.start() == range.end() {
+        return Ok(lo);
+    }
+    let hi = f(*range.end())?;
+    Ok(*lo.start().min(hi.start())..=*lo.end().max(hi.end()))
+}
+
+/// Error from either leg of a [`LstToLstCalc`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LstToLstCalcErr<I, O> {
+    /// Error from [`LstToLstCalc::inp`]
+    Inp(I),
+
+    /// Error from [`LstToLstCalc::out`]
+    Out(O),
+}
+
+impl<I: Display, O: Display> Display for LstToLstCalcErr<I, O> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inp(e) => write!(f, "inp calc: {e}"),
+            Self::Out(e) => write!(f, "out calc: {e}"),
+        }
+    }
+}
+
+impl<I: Error, O: Error> Error for LstToLstCalcErr<I, O> {}
@@ -1,5 +1,7 @@
 use core::ops::{Deref, RangeInclusive};
 
+use sanctum_u64_ratio::Ratio;
+
 pub trait SolValCalc {
     type Error: core::error::Error;
 
@@ -25,6 +27,72 @@ where
     }
 }
 
+/// Exact exchange rates of a calculator, in lamports per LST atom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LstToSolRatios {
+    /// Exchange rate before any fees are charged
+    pub pre_fee: Ratio<u64, u64>,
+
+    /// Effective exchange rate after fees are charged.
+    ///
+    /// This is the exact product of `pre_fee` and `1 - fee`.
+    /// Its numerator and denominator are each the product of 2 u64s,
+    /// so `u128` is required to keep it exact:
+    /// fitting it into a `Ratio<u64, u64>` would require reducing it,
+    /// which is not always possible without rounding.
+    pub post_fee: Ratio<u128, u128>,
+}
+
+/// Constructors
+impl LstToSolRatios {
+    #[inline]
+    pub const fn no_fee(pre_fee: Ratio<u64, u64>) -> Self {
+        Self::with_one_minus_fee(pre_fee, Ratio { n: 1, d: 1 })
+    }
+
+    /// `post_fee` is the unreduced product `pre_fee * one_minus_fee`.
+    ///
+    /// Denominators are multiplied as-is, so if either has `d = 0`,
+    /// e.g. `pre_fee` of a pool with 0 LST supply, `post_fee.d` is also 0.
+    /// Such ratios are not valid exchange rates,
+    /// callers must check for `d = 0` before dividing by it.
+    #[inline]
+    pub const fn with_one_minus_fee(
+        pre_fee: Ratio<u64, u64>,
+        one_minus_fee: Ratio<u64, u64>,
+    ) -> Self {
+        // unchecked-arith: product of 2 u64s does not overflow u128
+        let post_fee = Ratio {
+            n: pre_fee.n as u128 * one_minus_fee.n as u128,
+            d: pre_fee.d as u128 * one_minus_fee.d as u128,
+        };
+        Self { pre_fee, post_fee }
+    }
+}
+
+/// Companion to [`SolValCalc`] for the underlying exchange rate
+/// instead of quotes for specific amounts
+pub trait SolValCalcRatios {
+    type Error: core::error::Error;
+
+    /// Should fail under the same conditions as [`SolValCalc::lst_to_sol`]
+    /// regardless of amount, e.g. if the pool has not been updated for this epoch
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error>;
+}
+
+/// Blanket for refs
+impl<R, T: SolValCalcRatios> SolValCalcRatios for R
+where
+    R: Deref<Target = T>,
+{
+    type Error = T::Error;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.deref().lst_to_sol_ratios()
+    }
+}
+
 /// Suffix account meta slices returned by the 3 methods
 /// - must all have the same length
 /// - must all have length <= u8::MAX
@@ -72,3 +140,29 @@ where
         self.deref().suf_is_signer()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_one_minus_fee_zero_denom() {
+        let r = LstToSolRatios::with_one_minus_fee(Ratio { n: 5, d: 0 }, Ratio { n: 9, d: 10 });
+        assert_eq!(r.pre_fee, Ratio { n: 5, d: 0 });
+        assert_eq!(r.post_fee, Ratio { n: 45, d: 0 });
+
+        let r = LstToSolRatios::with_one_minus_fee(Ratio { n: 5, d: 4 }, Ratio { n: 0, d: 0 });
+        assert_eq!(r.post_fee, Ratio { n: 0, d: 0 });
+    }
+
+    #[test]
+    fn with_one_minus_fee_max_no_overflow() {
+        let max = Ratio {
+            n: u64::MAX,
+            d: u64::MAX,
+        };
+        let r = LstToSolRatios::with_one_minus_fee(max, max);
+        let sq = u64::MAX as u128 * u64::MAX as u128;
+        assert_eq!(r.post_fee, Ratio { n: sq, d: sq });
+    }
+}
@@ -0,0 +1,79 @@
+//! `no_std` traits for updating calculators with fetched accounts
+
+use core::{error::Error, iter::Flatten, option};
+
+// Re-exports
+pub use sanctum_update_traits::{Account, AccountOwner, UpdateErr, UpdateMap};
+
+pub trait AccountsToUpdateSvc {
+    type PkIter: Iterator<Item = [u8; 32]>;
+
+    /// Returned iterator can yield duplicate pubkeys,
+    /// responsibility of caller to dedup if required
+    fn accounts_to_update_svc(&self) -> Self::PkIter;
+}
+
+/// Accounts required for opt-in stake pool program upgrade detection,
+/// `[calculator program state, stake pool program data]`.
+/// Empty if upgrade detection is disabled.
+pub type UpgradeCheckPkIter = Flatten<option::IntoIter<[[u8; 32]; 2]>>;
+
+#[inline]
+pub fn upgrade_check_pk_iter(
+    is_enabled: bool,
+    state: [u8; 32],
+    pool_progdata: [u8; 32],
+) -> UpgradeCheckPkIter {
+    is_enabled
+        .then_some([state, pool_progdata])
+        .into_iter()
+        .flatten()
+}
+
+/// Offset of `epoch` in the `Clock` sysvar account data
+pub const CLOCK_EPOCH_OFFSET: usize = 16;
+
+/// Reads `epoch` from `Clock` sysvar account data.
+///
+/// Returns `None` if `clock_acc_data` is too short.
+#[inline]
+pub fn epoch_from_clock_data(clock_acc_data: &[u8]) -> Option<u64> {
+    u64_le_at(clock_acc_data, CLOCK_EPOCH_OFFSET)
+}
+
+/// Reads a little-endian `u64` at byte offset `at` of `data`
+#[inline]
+pub fn u64_le_at(data: &[u8], at: usize) -> Option<u64> {
+    chunk_at(data, at).map(|c| u64::from_le_bytes(*c))
+}
+
+/// `N` bytes at byte offset `at` of `data`
+#[inline]
+pub fn chunk_at<const N: usize>(data: &[u8], at: usize) -> Option<&[u8; N]> {
+    data.get(at..).and_then(|s| s.first_chunk())
+}
+
+pub trait UpdateSvc {
+    type InnerErr: Error;
+
+    fn update_svc(&mut self, update_map: impl UpdateMap) -> Result<(), UpdateErr<Self::InnerErr>>;
+}
+
+// TODO: might need a new trait if a different set of accounts
+// compared to quoting/calc-ing is required for instruction formation
+// for some svc programs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_from_clock_data_len() {
+        let mut data = [0u8; 40];
+        data[16..24].copy_from_slice(&123u64.to_le_bytes());
+        assert_eq!(epoch_from_clock_data(&data), Some(123));
+        assert_eq!(epoch_from_clock_data(&data[..24]), Some(123));
+        assert_eq!(epoch_from_clock_data(&data[..23]), None);
+        assert_eq!(epoch_from_clock_data(&[]), None);
+    }
+}
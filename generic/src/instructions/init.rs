@@ -0,0 +1,82 @@
+use generic_array_struct::generic_array_struct;
+
+use super::{DiscmOnlyIxData, IxSufKeysOwned, DISCM_ONLY_IX_DATA_LEN};
+
+// Accounts
+
+#[generic_array_struct(builder pub)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InitIxAccs<T> {
+    pub payer: T,
+    pub state: T,
+    pub pool_prog: T,
+    pub pool_progdata: T,
+    pub system_program: T,
+}
+
+impl<T: Copy> InitIxAccs<T> {
+    #[inline]
+    pub const fn memset(val: T) -> Self {
+        Self([val; INIT_IX_ACCS_LEN])
+    }
+}
+
+impl<T> AsRef<[T]> for InitIxAccs<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+pub type InitIxKeys<'a> = InitIxAccs<&'a [u8; 32]>;
+
+pub type InitIxKeysOwned = InitIxAccs<[u8; 32]>;
+
+pub type InitIxAccFlags = InitIxAccs<bool>;
+
+pub const INIT_IX_IS_WRITER: InitIxAccFlags = InitIxAccFlags::memset(false)
+    .const_with_payer(true)
+    .const_with_state(true);
+
+pub const INIT_IX_IS_SIGNER: InitIxAccFlags = InitIxAccFlags::memset(false).const_with_payer(true);
+
+impl InitIxKeys<'_> {
+    #[inline]
+    pub fn into_owned(&self) -> InitIxKeysOwned {
+        InitIxAccs(self.0.map(|p| *p))
+    }
+}
+
+impl InitIxKeysOwned {
+    #[inline]
+    pub fn as_borrowed(&self) -> InitIxKeys<'_> {
+        InitIxAccs(self.0.each_ref())
+    }
+}
+
+/// `11111111111111111111111111111111`
+pub const SYSTEM_PROGRAM_ID: [u8; 32] = [0u8; 32];
+
+/// Constructors
+impl InitIxKeysOwned {
+    /// `suf` is the calculator program's SolValCalc interface instruction
+    /// accounts suffix, from which all calculator-program-specific keys are taken
+    #[inline]
+    pub const fn of_svc_suf(payer: [u8; 32], suf: &IxSufKeysOwned) -> Self {
+        Self::memset([0u8; 32])
+            .const_with_payer(payer)
+            .const_with_state(*suf.state())
+            .const_with_pool_prog(*suf.pool_prog())
+            .const_with_pool_progdata(*suf.pool_progdata())
+            .const_with_system_program(SYSTEM_PROGRAM_ID)
+    }
+}
+
+// Data
+
+pub const INIT_IX_DISCM: u8 = 4;
+
+pub const INIT_IX_DATA_LEN: usize = DISCM_ONLY_IX_DATA_LEN;
+
+pub type InitIxData = DiscmOnlyIxData<INIT_IX_DISCM>;
@@ -1,9 +1,15 @@
 use generic_array_struct::generic_array_struct;
 
+pub mod init;
+
 pub mod lst_to_sol;
 
+pub mod set_manager;
+
 pub mod sol_to_lst;
 
+pub mod update_last_upgrade_slot;
+
 #[generic_array_struct(builder pub)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -51,3 +57,25 @@ impl IxSufKeysOwned {
         IxSufAccs(self.0.each_ref())
     }
 }
+
+// Admin instructions data
+
+/// Instruction data of instructions that take no args
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiscmOnlyIxData<const DISCM: u8>;
+
+pub const DISCM_ONLY_IX_DATA_LEN: usize = 1;
+
+impl<const DISCM: u8> DiscmOnlyIxData<DISCM> {
+    const BUF: [u8; DISCM_ONLY_IX_DATA_LEN] = [DISCM];
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    #[inline]
+    pub const fn as_buf(&self) -> &'static [u8; DISCM_ONLY_IX_DATA_LEN] {
+        &Self::BUF
+    }
+}
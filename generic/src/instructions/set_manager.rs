@@ -0,0 +1,79 @@
+use generic_array_struct::generic_array_struct;
+
+use super::{DiscmOnlyIxData, IxSufKeysOwned, DISCM_ONLY_IX_DATA_LEN};
+
+// Accounts
+
+#[generic_array_struct(builder pub)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SetManagerIxAccs<T> {
+    pub manager: T,
+    pub new_manager: T,
+    pub state: T,
+}
+
+impl<T: Copy> SetManagerIxAccs<T> {
+    #[inline]
+    pub const fn memset(val: T) -> Self {
+        Self([val; SET_MANAGER_IX_ACCS_LEN])
+    }
+}
+
+impl<T> AsRef<[T]> for SetManagerIxAccs<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+pub type SetManagerIxKeys<'a> = SetManagerIxAccs<&'a [u8; 32]>;
+
+pub type SetManagerIxKeysOwned = SetManagerIxAccs<[u8; 32]>;
+
+pub type SetManagerIxAccFlags = SetManagerIxAccs<bool>;
+
+pub const SET_MANAGER_IX_IS_WRITER: SetManagerIxAccFlags =
+    SetManagerIxAccFlags::memset(false).const_with_state(true);
+
+pub const SET_MANAGER_IX_IS_SIGNER: SetManagerIxAccFlags =
+    SetManagerIxAccFlags::memset(false).const_with_manager(true);
+
+impl SetManagerIxKeys<'_> {
+    #[inline]
+    pub fn into_owned(&self) -> SetManagerIxKeysOwned {
+        SetManagerIxAccs(self.0.map(|p| *p))
+    }
+}
+
+impl SetManagerIxKeysOwned {
+    #[inline]
+    pub fn as_borrowed(&self) -> SetManagerIxKeys<'_> {
+        SetManagerIxAccs(self.0.each_ref())
+    }
+}
+
+/// Constructors
+impl SetManagerIxKeysOwned {
+    /// `suf` is the calculator program's SolValCalc interface instruction
+    /// accounts suffix, from which all calculator-program-specific keys are taken
+    #[inline]
+    pub const fn of_svc_suf(
+        manager: [u8; 32],
+        new_manager: [u8; 32],
+        suf: &IxSufKeysOwned,
+    ) -> Self {
+        Self::memset([0u8; 32])
+            .const_with_manager(manager)
+            .const_with_new_manager(new_manager)
+            .const_with_state(*suf.state())
+    }
+}
+
+// Data
+
+pub const SET_MANAGER_IX_DISCM: u8 = 3;
+
+pub const SET_MANAGER_IX_DATA_LEN: usize = DISCM_ONLY_IX_DATA_LEN;
+
+pub type SetManagerIxData = DiscmOnlyIxData<SET_MANAGER_IX_DISCM>;
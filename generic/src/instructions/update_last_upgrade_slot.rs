@@ -0,0 +1,77 @@
+use generic_array_struct::generic_array_struct;
+
+use super::{DiscmOnlyIxData, IxSufKeysOwned, DISCM_ONLY_IX_DATA_LEN};
+
+// Accounts
+
+#[generic_array_struct(builder pub)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UpdateLastUpgradeSlotIxAccs<T> {
+    pub manager: T,
+    pub state: T,
+    pub pool_prog: T,
+    pub pool_progdata: T,
+}
+
+impl<T: Copy> UpdateLastUpgradeSlotIxAccs<T> {
+    #[inline]
+    pub const fn memset(val: T) -> Self {
+        Self([val; UPDATE_LAST_UPGRADE_SLOT_IX_ACCS_LEN])
+    }
+}
+
+impl<T> AsRef<[T]> for UpdateLastUpgradeSlotIxAccs<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+pub type UpdateLastUpgradeSlotIxKeys<'a> = UpdateLastUpgradeSlotIxAccs<&'a [u8; 32]>;
+
+pub type UpdateLastUpgradeSlotIxKeysOwned = UpdateLastUpgradeSlotIxAccs<[u8; 32]>;
+
+pub type UpdateLastUpgradeSlotIxAccFlags = UpdateLastUpgradeSlotIxAccs<bool>;
+
+pub const UPDATE_LAST_UPGRADE_SLOT_IX_IS_WRITER: UpdateLastUpgradeSlotIxAccFlags =
+    UpdateLastUpgradeSlotIxAccFlags::memset(false).const_with_state(true);
+
+pub const UPDATE_LAST_UPGRADE_SLOT_IX_IS_SIGNER: UpdateLastUpgradeSlotIxAccFlags =
+    UpdateLastUpgradeSlotIxAccFlags::memset(false).const_with_manager(true);
+
+impl UpdateLastUpgradeSlotIxKeys<'_> {
+    #[inline]
+    pub fn into_owned(&self) -> UpdateLastUpgradeSlotIxKeysOwned {
+        UpdateLastUpgradeSlotIxAccs(self.0.map(|p| *p))
+    }
+}
+
+impl UpdateLastUpgradeSlotIxKeysOwned {
+    #[inline]
+    pub fn as_borrowed(&self) -> UpdateLastUpgradeSlotIxKeys<'_> {
+        UpdateLastUpgradeSlotIxAccs(self.0.each_ref())
+    }
+}
+
+/// Constructors
+impl UpdateLastUpgradeSlotIxKeysOwned {
+    /// `suf` is the calculator program's SolValCalc interface instruction
+    /// accounts suffix, from which all calculator-program-specific keys are taken
+    #[inline]
+    pub const fn of_svc_suf(manager: [u8; 32], suf: &IxSufKeysOwned) -> Self {
+        Self::memset([0u8; 32])
+            .const_with_manager(manager)
+            .const_with_state(*suf.state())
+            .const_with_pool_prog(*suf.pool_prog())
+            .const_with_pool_progdata(*suf.pool_progdata())
+    }
+}
+
+// Data
+
+pub const UPDATE_LAST_UPGRADE_SLOT_IX_DISCM: u8 = 2;
+
+pub const UPDATE_LAST_UPGRADE_SLOT_IX_DATA_LEN: usize = DISCM_ONLY_IX_DATA_LEN;
+
+pub type UpdateLastUpgradeSlotIxData = DiscmOnlyIxData<UPDATE_LAST_UPGRADE_SLOT_IX_DISCM>;
@@ -0,0 +1,88 @@
+use core::mem::{align_of, size_of};
+
+/// The single `pda("state")` account of a generic sol value calculator program
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct ProgramState {
+    pub manager: [u8; 32],
+
+    /// little-endian u64.
+    /// Last recorded upgrade slot of the stake pool program
+    last_upgrade_slot: [u8; 8],
+}
+
+pub const PROGRAM_STATE_SIZE: usize = size_of::<ProgramState>();
+
+const _ASSERT_PROGRAM_STATE_SIZE: () = assert!(PROGRAM_STATE_SIZE == 40);
+
+// required for zero-copy casts from arbitrary byte slices to be valid
+const _ASSERT_PROGRAM_STATE_ALIGN: () = assert!(align_of::<ProgramState>() == 1);
+
+/// Constructors
+impl ProgramState {
+    #[inline]
+    pub const fn new(manager: [u8; 32], last_upgrade_slot: u64) -> Self {
+        Self {
+            manager,
+            last_upgrade_slot: last_upgrade_slot.to_le_bytes(),
+        }
+    }
+}
+
+/// Accessors
+impl ProgramState {
+    #[inline]
+    pub const fn last_upgrade_slot(&self) -> u64 {
+        u64::from_le_bytes(self.last_upgrade_slot)
+    }
+
+    #[inline]
+    pub const fn set_last_upgrade_slot(&mut self, last_upgrade_slot: u64) {
+        self.last_upgrade_slot = last_upgrade_slot.to_le_bytes();
+    }
+}
+
+/// Serde
+impl ProgramState {
+    /// Returns `None` if `acc_data` is not exactly [`PROGRAM_STATE_SIZE`] long
+    #[inline]
+    pub const fn of_acc_data(acc_data: &[u8]) -> Option<&Self> {
+        if acc_data.len() != PROGRAM_STATE_SIZE {
+            return None;
+        }
+        match acc_data.first_chunk() {
+            Some(buf) => Some(Self::of_buf(buf)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if `acc_data` is not exactly [`PROGRAM_STATE_SIZE`] long
+    #[inline]
+    pub const fn of_acc_data_mut(acc_data: &mut [u8]) -> Option<&mut Self> {
+        if acc_data.len() != PROGRAM_STATE_SIZE {
+            return None;
+        }
+        match acc_data.first_chunk_mut() {
+            Some(buf) => Some(Self::of_buf_mut(buf)),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub const fn of_buf(buf: &[u8; PROGRAM_STATE_SIZE]) -> &Self {
+        // safety: repr(C), align = 1 and size = PROGRAM_STATE_SIZE means cast is valid
+        unsafe { &*buf.as_ptr().cast() }
+    }
+
+    #[inline]
+    pub const fn of_buf_mut(buf: &mut [u8; PROGRAM_STATE_SIZE]) -> &mut Self {
+        // safety: repr(C), align = 1 and size = PROGRAM_STATE_SIZE means cast is valid
+        unsafe { &mut *buf.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    pub const fn as_buf(&self) -> &[u8; PROGRAM_STATE_SIZE] {
+        // safety: repr(C), no padding and size = PROGRAM_STATE_SIZE means cast is valid
+        unsafe { &*(self as *const Self).cast() }
+    }
+}
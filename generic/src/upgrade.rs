@@ -0,0 +1,140 @@
+//! Opt-in detection of stake pool program upgrades.
+//!
+//! Generic sol value calculator programs refuse to quote if the stake pool program
+//! has been upgraded since the `last_upgrade_slot` recorded in their [`crate::state::ProgramState`].
+//! Off-chain SDKs that do not check this can give quotes that would fail on-chain.
+
+use core::{error::Error, fmt::Display};
+
+#[cfg(feature = "update")]
+use sanctum_update_traits::{Account, UpdateErr, UpdateMap};
+
+#[cfg(feature = "update")]
+use crate::state::ProgramState;
+
+/// Length of the header of a BPF upgradeable loader `ProgramData` account:
+/// - u32 enum discriminant
+/// - u64 slot
+/// - `Option<Pubkey>` upgrade authority
+///
+/// Only this prefix of the (huge) account data is required for upgrade detection,
+/// so fetchers can request just this slice (e.g. RPC `dataSlice`).
+pub const PROGDATA_HEADER_LEN: usize = 45;
+
+const PROGDATA_DISCM: u32 = 3;
+
+/// Returns `None` if `progdata_acc_data` does not start with a valid `ProgramData` header.
+/// Only requires the first 12 bytes of account data.
+#[inline]
+pub const fn progdata_last_upgrade_slot(progdata_acc_data: &[u8]) -> Option<u64> {
+    match progdata_acc_data.split_first_chunk::<4>() {
+        Some((discm, rem)) if u32::from_le_bytes(*discm) == PROGDATA_DISCM => {
+            match rem.first_chunk::<8>() {
+                Some(slot) => Some(u64::from_le_bytes(*slot)),
+                None => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LastUpgradeSlots {
+    /// As recorded in the calculator program's [`crate::state::ProgramState`]
+    pub state: u64,
+
+    /// As recorded in the stake pool program's `ProgramData`
+    pub pool_progdata: u64,
+}
+
+impl LastUpgradeSlots {
+    #[inline]
+    pub const fn is_upgraded(&self) -> bool {
+        self.state != self.pool_progdata
+    }
+
+    #[inline]
+    pub const fn check(&self) -> Result<(), PoolProgUpgradedErr> {
+        if self.is_upgraded() {
+            Err(PoolProgUpgradedErr(*self))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpgradeCheck {
+    #[default]
+    Disabled,
+
+    /// Might be `None` at initialization before accounts required
+    /// to perform the check have been fetched
+    Enabled(Option<LastUpgradeSlots>),
+}
+
+impl UpgradeCheck {
+    #[inline]
+    pub const fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled(_))
+    }
+
+    /// Always `Ok` if disabled or required accounts have not been fetched yet
+    #[inline]
+    pub const fn check(&self) -> Result<(), PoolProgUpgradedErr> {
+        match self {
+            Self::Enabled(Some(slots)) => slots.check(),
+            Self::Enabled(None) | Self::Disabled => Ok(()),
+        }
+    }
+}
+
+/// Returns `upgrade_check` unchanged if disabled, otherwise reads the last upgrade slots
+/// from the calculator program's `state` account and the stake pool program's
+/// `pool_progdata` account in `update_map`.
+///
+/// `acc_deser_err` creates the error for an account whose data could not be deserialized.
+#[cfg(feature = "update")]
+#[inline]
+pub fn updated_upgrade_check<E>(
+    upgrade_check: UpgradeCheck,
+    state: &[u8; 32],
+    pool_progdata: &[u8; 32],
+    update_map: impl UpdateMap,
+    acc_deser_err: impl Fn([u8; 32]) -> E,
+) -> Result<UpgradeCheck, UpdateErr<E>> {
+    if !upgrade_check.is_enabled() {
+        return Ok(upgrade_check);
+    }
+    let state_acc = update_map.get_account_checked(state)?;
+    let state_last_upgrade_slot = ProgramState::of_acc_data(state_acc.data())
+        .map(|s| s.last_upgrade_slot())
+        .ok_or_else(|| UpdateErr::Inner(acc_deser_err(*state)))?;
+    let progdata_acc = update_map.get_account_checked(pool_progdata)?;
+    let pool_last_upgrade_slot = progdata_last_upgrade_slot(progdata_acc.data())
+        .ok_or_else(|| UpdateErr::Inner(acc_deser_err(*pool_progdata)))?;
+    Ok(UpgradeCheck::Enabled(Some(LastUpgradeSlots {
+        state: state_last_upgrade_slot,
+        pool_progdata: pool_last_upgrade_slot,
+    })))
+}
+
+/// Contains the mismatched slots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolProgUpgradedErr(pub LastUpgradeSlots);
+
+impl Display for PoolProgUpgradedErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let LastUpgradeSlots {
+            state,
+            pool_progdata,
+        } = self.0;
+        write!(
+            f,
+            "pool program upgraded at slot {pool_progdata}, last recorded upgrade slot {state}"
+        )
+    }
+}
+
+impl Error for PoolProgUpgradedErr {}
@@ -1,14 +1,19 @@
-use core::ops::RangeInclusive;
+use core::{error::Error, fmt::Display, ops::RangeInclusive};
 
 use jiminy_cpi::{
     account::{Abr, AccountHandle},
-    program_error::{ProgramError, BORSH_IO_ERROR},
+    program_error::ProgramError,
     Cpi, CpiBuilder,
 };
 use jiminy_return_data::get_return_data;
+use sanctum_svc_ag_core::instructions::SvcCalcAccsAgRef;
 use sanctum_svc_core::instructions::{
-    lst_to_sol::LstToSolIxData, sol_to_lst::SolToLstIxData, IxAccs, IX_DATA_LEN,
+    lst_to_sol::LstToSolIxData,
+    return_data::{decode_return_data, ReturnDataErr, RETURN_DATA_LEN},
+    sol_to_lst::SolToLstIxData,
+    IxAccs, IX_DATA_LEN,
 };
+use sanctum_svc_core::traits::SolValCalcAccs;
 
 pub type IxAccountHandles<'a, P> = IxAccs<AccountHandle<'a>, P>;
 
@@ -20,14 +25,15 @@ pub fn cpi_sol_to_lst<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
     lamports: u64,
     accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
 ) -> Result<RangeInclusive<u64>, ProgramError> {
+    let svc_prog_id = *abr.get(svc_prog).key();
     prepare(
         cpi,
         abr,
         svc_prog,
         SolToLstIxData::new(lamports).as_buf(),
-        accs,
+        accs.seq().copied(),
     )
-    .and_then(invoke)
+    .and_then(|cpi| invoke(cpi, &svc_prog_id))
 }
 
 /// [`cpi_sol_to_lst`] but using a svc program address instead of handle
@@ -44,9 +50,9 @@ pub fn cpi_sol_to_lst_id<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
         abr,
         svc_prog,
         SolToLstIxData::new(lamports).as_buf(),
-        accs,
+        accs.seq().copied(),
     )
-    .and_then(invoke)
+    .and_then(|cpi| invoke(cpi, svc_prog))
 }
 
 #[inline]
@@ -57,14 +63,15 @@ pub fn cpi_lst_to_sol<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
     lst_amt: u64,
     accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
 ) -> Result<RangeInclusive<u64>, ProgramError> {
+    let svc_prog_id = *abr.get(svc_prog).key();
     prepare(
         cpi,
         abr,
         svc_prog,
         LstToSolIxData::new(lst_amt).as_buf(),
-        accs,
+        accs.seq().copied(),
     )
-    .and_then(invoke)
+    .and_then(|cpi| invoke(cpi, &svc_prog_id))
 }
 
 /// [`cpi_lst_to_sol`] but using a svc program address instead of handle
@@ -81,54 +88,333 @@ pub fn cpi_lst_to_sol_id<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
         abr,
         svc_prog,
         LstToSolIxData::new(lst_amt).as_buf(),
-        accs,
+        accs.seq().copied(),
     )
-    .and_then(invoke)
+    .and_then(|cpi| invoke(cpi, svc_prog))
 }
 
-// just splitting prepare() and invoke() into 2 fns here
-// in case we need to expose them to public in the future
+// Checked
 
+/// [`cpi_sol_to_lst`], but first checks `svc_prog` and `accs`' suffix
+/// against `calc_accs`, see [`check_svc_accs`]
 #[inline]
-fn prepare<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+pub fn cpi_sol_to_lst_checked<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
     cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
     abr: &'cpi mut Abr,
     svc_prog: AccountHandle<'accounts>,
-    ix_data: &'cpi [u8; IX_DATA_LEN],
+    lamports: u64,
+    accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    calc_accs: SvcCalcAccsAgRef<'_>,
+) -> Result<RangeInclusive<u64>, ProgramError> {
+    check_svc_accs(abr, abr.get(svc_prog).key(), accs, calc_accs)?;
+    cpi_sol_to_lst(cpi, abr, svc_prog, lamports, accs)
+}
+
+/// [`cpi_sol_to_lst_id`], but first checks `svc_prog` and `accs`' suffix
+/// against `calc_accs`, see [`check_svc_accs`]
+#[inline]
+pub fn cpi_sol_to_lst_id_checked<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+    cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
+    abr: &'cpi mut Abr,
+    svc_prog: &'cpi [u8; 32],
+    lamports: u64,
+    accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    calc_accs: SvcCalcAccsAgRef<'_>,
+) -> Result<RangeInclusive<u64>, ProgramError> {
+    check_svc_accs(abr, svc_prog, accs, calc_accs)?;
+    cpi_sol_to_lst_id(cpi, abr, svc_prog, lamports, accs)
+}
+
+/// [`cpi_lst_to_sol`], but first checks `svc_prog` and `accs`' suffix
+/// against `calc_accs`, see [`check_svc_accs`]
+#[inline]
+pub fn cpi_lst_to_sol_checked<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+    cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
+    abr: &'cpi mut Abr,
+    svc_prog: AccountHandle<'accounts>,
+    lst_amt: u64,
+    accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    calc_accs: SvcCalcAccsAgRef<'_>,
+) -> Result<RangeInclusive<u64>, ProgramError> {
+    check_svc_accs(abr, abr.get(svc_prog).key(), accs, calc_accs)?;
+    cpi_lst_to_sol(cpi, abr, svc_prog, lst_amt, accs)
+}
+
+/// [`cpi_lst_to_sol_id`], but first checks `svc_prog` and `accs`' suffix
+/// against `calc_accs`, see [`check_svc_accs`]
+#[inline]
+pub fn cpi_lst_to_sol_id_checked<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+    cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
+    abr: &'cpi mut Abr,
+    svc_prog: &'cpi [u8; 32],
+    lst_amt: u64,
     accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    calc_accs: SvcCalcAccsAgRef<'_>,
+) -> Result<RangeInclusive<u64>, ProgramError> {
+    check_svc_accs(abr, svc_prog, accs, calc_accs)?;
+    cpi_lst_to_sol_id(cpi, abr, svc_prog, lst_amt, accs)
+}
+
+/// Checks that
+/// - `svc_prog` is the calculator program of `calc_accs`, [`SvcAg::svc_program_id`]
+/// - `accs`' suffix keys are exactly [`SolValCalcAccs::suf_keys_owned`] of `calc_accs`
+///
+/// [`SvcAg::svc_program_id`]: sanctum_svc_ag_core::SvcAg::svc_program_id
+#[inline]
+pub fn check_svc_accs<'accounts>(
+    abr: &Abr,
+    svc_prog: &[u8; 32],
+    accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    calc_accs: SvcCalcAccsAgRef<'_>,
+) -> Result<(), SvcCpiErr> {
+    if svc_prog != calc_accs.svc_program_id() {
+        return Err(SvcCpiErr::WrongSvcProg);
+    }
+    let expected = calc_accs.suf_keys_owned();
+    let (expected, actual) = (expected.as_ref(), accs.suf.as_ref());
+    if expected.len() != actual.len() {
+        return Err(SvcCpiErr::SufAccsLen);
+    }
+    if actual
+        .iter()
+        .zip(expected)
+        .any(|(h, expected)| abr.get(*h).key() != expected)
+    {
+        return Err(SvcCpiErr::WrongSufAcc);
+    }
+    Ok(())
+}
+
+// Composable steps
+
+/// First step of a SolValCalc CPI: sets the program, instruction data and accounts.
+///
+/// `accs` is usually [`IxAccs::seq`] of an [`IxAccountHandles`],
+/// but can have extra trailing accounts chained on for future interface versions.
+#[inline]
+pub fn prepare<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+    cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
+    abr: &'cpi mut Abr,
+    svc_prog: AccountHandle<'accounts>,
+    ix_data: &'cpi [u8; IX_DATA_LEN],
+    accs: impl IntoIterator<Item = AccountHandle<'accounts>>,
 ) -> Result<CpiBuilder<'cpi, MAX_CPI_ACCS, true>, ProgramError> {
     CpiBuilder::new(cpi, abr)
         .with_prog_handle(svc_prog)
         .with_ix_data(ix_data)
-        .with_accounts_fwd(accs.seq().copied())
+        .with_accounts_fwd(accs)
 }
 
 /// [`prepare`] but using a svc program address instead of handle
 #[inline]
-fn prepare_id<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
+pub fn prepare_id<'cpi, 'accounts, const MAX_CPI_ACCS: usize>(
     cpi: &'cpi mut Cpi<MAX_CPI_ACCS>,
     abr: &'cpi mut Abr,
     svc_prog: &'cpi [u8; 32],
     ix_data: &'cpi [u8; IX_DATA_LEN],
-    accs: &IxAccountHandles<'accounts, impl AsRef<[AccountHandle<'accounts>]>>,
+    accs: impl IntoIterator<Item = AccountHandle<'accounts>>,
 ) -> Result<CpiBuilder<'cpi, MAX_CPI_ACCS, true>, ProgramError> {
     CpiBuilder::new(cpi, abr)
         .with_prog_id(svc_prog)
         .with_ix_data(ix_data)
-        .with_accounts_fwd(accs.seq().copied())
+        .with_accounts_fwd(accs)
 }
 
+/// Second step of a SolValCalc CPI: invokes the program
+/// then reads its output range with [`return_data_range`].
+///
+/// `svc_prog_id` must be the program set in [`prepare`] or [`prepare_id`].
+///
+/// To invoke with PDA signer seeds instead, call `invoke_signed` on the
+/// `CpiBuilder` returned by [`prepare`] followed by [`return_data_range`]:
+///
+/// ```ignore
+/// let svc_prog_id = *abr.get(svc_prog).key();
+/// let ix_data = LstToSolIxData::new(lst_amt);
+/// prepare(cpi, abr, svc_prog, ix_data.as_buf(), accs.seq().copied())?
+///     .invoke_signed(&[PdaSigner::new(&[PdaSeed::new(b"seed"), PdaSeed::new(&[bump])])])?;
+/// let range = return_data_range(&svc_prog_id)?;
+/// ```
 #[inline]
-fn invoke<const MAX_CPI_ACCS: usize>(
+pub fn invoke<const MAX_CPI_ACCS: usize>(
     cpi: CpiBuilder<'_, MAX_CPI_ACCS, true>,
+    svc_prog_id: &[u8; 32],
 ) -> Result<RangeInclusive<u64>, ProgramError> {
     cpi.invoke()?;
-    let data_opt = get_return_data::<16>();
-    let (min, max) = data_opt
-        .as_ref()
-        .map(|d| d.data())
-        .and_then(|s| s.split_first_chunk::<8>())
-        .and_then(|(min, rem)| rem.first_chunk::<8>().map(|max| (min, max)))
-        .ok_or(BORSH_IO_ERROR)?;
-    Ok(u64::from_le_bytes(*min)..=u64::from_le_bytes(*max))
+    return_data_range(svc_prog_id)
+}
+
+/// Reads the output range of a SolValCalc CPI to `svc_prog_id` that was just invoked.
+///
+/// Errors if the SolValCalc program did not set return data,
+/// or if the return data is not valid, see [`check_return_data`].
+///
+/// For return data of a different size, e.g. future interface versions,
+/// call `jiminy_return_data::get_return_data` with the new size directly instead.
+#[inline]
+pub fn return_data_range(svc_prog_id: &[u8; 32]) -> Result<RangeInclusive<u64>, ProgramError> {
+    // 1 extra byte to detect return data that is too long
+    let data_opt = get_return_data::<{ RETURN_DATA_LEN + 1 }>();
+    let ret = data_opt.as_ref().map(|d| (d.program_id(), d.data()));
+    check_return_data(svc_prog_id, ret).map_err(ProgramError::from)
+}
+
+/// Checks `return_data`, `(program_id, data)`, of a SolValCalc CPI to `svc_prog_id`:
+/// - return data was set
+/// - return data was set by `svc_prog_id` and not left over from an earlier CPI
+/// - data is exactly [`RETURN_DATA_LEN`] long
+/// - decoded `min <= max`
+#[inline]
+pub fn check_return_data(
+    svc_prog_id: &[u8; 32],
+    return_data: Option<(&[u8; 32], &[u8])>,
+) -> Result<RangeInclusive<u64>, SvcCpiErr> {
+    let (program_id, data) = return_data.ok_or(SvcCpiErr::NoReturnData)?;
+    if program_id != svc_prog_id {
+        return Err(SvcCpiErr::WrongReturnDataProg);
+    }
+    decode_return_data(data).map_err(|e| match e {
+        ReturnDataErr::InvalidLen(_) => SvcCpiErr::ReturnDataLen,
+        ReturnDataErr::MinGtMax => SvcCpiErr::ReturnDataMinGtMax,
+    })
+}
+
+/// Base of [`SvcCpiErr`] custom program error codes,
+/// to avoid clashing with the calling program's own error codes
+pub const SVC_CPI_ERR_BASE: u32 = 0x5356_4300;
+
+/// Custom [`ProgramError`]s returned by the checked CPI helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum SvcCpiErr {
+    /// SolValCalc program is not the expected program
+    WrongSvcProg = SVC_CPI_ERR_BASE,
+
+    /// Number of accounts suffix handles is not the number of expected accounts
+    SufAccsLen,
+
+    /// An accounts suffix handle's key is not the expected account
+    WrongSufAcc,
+
+    /// SolValCalc program did not set return data
+    NoReturnData,
+
+    /// Return data was set by a program other than the SolValCalc program
+    WrongReturnDataProg,
+
+    /// Return data was not exactly [`RETURN_DATA_LEN`] bytes long
+    ReturnDataLen,
+
+    /// Return data range start was greater than range end
+    ReturnDataMinGtMax,
+}
+
+impl SvcCpiErr {
+    #[inline]
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Display for SvcCpiErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::WrongSvcProg => "WrongSvcProg",
+            Self::SufAccsLen => "SufAccsLen",
+            Self::WrongSufAcc => "WrongSufAcc",
+            Self::NoReturnData => "NoReturnData",
+            Self::WrongReturnDataProg => "WrongReturnDataProg",
+            Self::ReturnDataLen => "ReturnDataLen",
+            Self::ReturnDataMinGtMax => "ReturnDataMinGtMax",
+        })
+    }
+}
+
+impl Error for SvcCpiErr {}
+
+impl From<SvcCpiErr> for ProgramError {
+    #[inline]
+    fn from(e: SvcCpiErr) -> Self {
+        ProgramError::custom(e.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sanctum_svc_core::instructions::return_data::encode_return_data;
+
+    use super::*;
+
+    const SVC_PROG: [u8; 32] = [1; 32];
+
+    const OTHER_PROG: [u8; 32] = [2; 32];
+
+    #[test]
+    fn check_return_data_ok() {
+        let data = encode_return_data(&(1..=2)).unwrap();
+        assert_eq!(
+            check_return_data(&SVC_PROG, Some((&SVC_PROG, data.as_slice()))),
+            Ok(1..=2)
+        );
+    }
+
+    #[test]
+    fn check_return_data_none() {
+        assert_eq!(
+            check_return_data(&SVC_PROG, None),
+            Err(SvcCpiErr::NoReturnData)
+        );
+    }
+
+    #[test]
+    fn check_return_data_wrong_prog() {
+        let data = encode_return_data(&(1..=2)).unwrap();
+        assert_eq!(
+            check_return_data(&SVC_PROG, Some((&OTHER_PROG, data.as_slice()))),
+            Err(SvcCpiErr::WrongReturnDataProg)
+        );
+    }
+
+    #[test]
+    fn check_return_data_len() {
+        let short = [0u8; RETURN_DATA_LEN - 1];
+        let long = [0u8; RETURN_DATA_LEN + 1];
+        [short.as_slice(), long.as_slice()]
+            .into_iter()
+            .for_each(|d| {
+                assert_eq!(
+                    check_return_data(&SVC_PROG, Some((&SVC_PROG, d))),
+                    Err(SvcCpiErr::ReturnDataLen)
+                );
+            });
+    }
+
+    #[test]
+    fn check_return_data_min_gt_max() {
+        let mut data = [0u8; RETURN_DATA_LEN];
+        data[..8].copy_from_slice(&2u64.to_le_bytes());
+        data[8..].copy_from_slice(&1u64.to_le_bytes());
+        assert_eq!(
+            check_return_data(&SVC_PROG, Some((&SVC_PROG, data.as_slice()))),
+            Err(SvcCpiErr::ReturnDataMinGtMax)
+        );
+    }
+
+    #[test]
+    fn err_codes_distinct() {
+        let codes = [
+            SvcCpiErr::WrongSvcProg,
+            SvcCpiErr::SufAccsLen,
+            SvcCpiErr::WrongSufAcc,
+            SvcCpiErr::NoReturnData,
+            SvcCpiErr::WrongReturnDataProg,
+            SvcCpiErr::ReturnDataLen,
+            SvcCpiErr::ReturnDataMinGtMax,
+        ]
+        .map(SvcCpiErr::code);
+        codes.iter().enumerate().for_each(|(i, c)| {
+            assert!(!codes[..i].contains(c));
+        });
+    }
 }
@@ -0,0 +1,51 @@
+//! Slicing the accounts of many SolValCalc instructions, each `[lst_mint, ...suffix]`,
+//! out of a single packed account list
+
+use jiminy_cpi::{
+    account::{Abr, AccountHandle},
+    program_error::{ProgramError, INCORRECT_PROGRAM_ID, NOT_ENOUGH_ACCOUNT_KEYS},
+};
+use sanctum_svc_ag_core::SvcAgTy;
+use sanctum_svc_core::instructions::{IxAccs, IxPreAccs, IX_PRE_ACCS_LEN};
+
+use crate::cpi::IxAccountHandles;
+
+/// Accounts of a single SolValCalc instruction sliced out of a packed account list
+pub type IxAccountHandlesSlice<'a, 'accounts> =
+    IxAccountHandles<'accounts, &'a [AccountHandle<'accounts>]>;
+
+/// Takes the accounts of a single SolValCalc instruction of `ty`,
+/// [`SvcAgTy::ix_accs_len`] accounts, off the front of `accounts`,
+/// advancing `accounts` past them.
+///
+/// Errors with `NOT_ENOUGH_ACCOUNT_KEYS` if `accounts` is too short,
+/// in which case `accounts` is left unchanged.
+#[inline]
+pub fn take_ix_accs<'a, 'accounts>(
+    ty: &SvcAgTy,
+    accounts: &mut &'a [AccountHandle<'accounts>],
+) -> Result<IxAccountHandlesSlice<'a, 'accounts>, ProgramError> {
+    let (pre, rest) = accounts
+        .split_first_chunk::<IX_PRE_ACCS_LEN>()
+        .ok_or(NOT_ENOUGH_ACCOUNT_KEYS)?;
+    let (suf, rest) = rest
+        .split_at_checked(ty.suf_len())
+        .ok_or(NOT_ENOUGH_ACCOUNT_KEYS)?;
+    let [lst_mint] = *pre;
+    *accounts = rest;
+    Ok(IxAccs::new(IxPreAccs::memset(lst_mint), suf))
+}
+
+/// [`take_ix_accs`], with the calculator type determined by `svc_prog`'s key.
+///
+/// Errors with `INCORRECT_PROGRAM_ID` if `svc_prog` is not a known SolValCalc program.
+#[inline]
+pub fn take_ix_accs_of_prog<'a, 'accounts>(
+    abr: &Abr,
+    svc_prog: AccountHandle<'accounts>,
+    accounts: &mut &'a [AccountHandle<'accounts>],
+) -> Result<(SvcAgTy, IxAccountHandlesSlice<'a, 'accounts>), ProgramError> {
+    let ty =
+        SvcAgTy::try_from_svc_program_id(abr.get(svc_prog).key()).ok_or(INCORRECT_PROGRAM_ID)?;
+    take_ix_accs(&ty, accounts).map(|accs| (ty, accs))
+}
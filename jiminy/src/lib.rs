@@ -1,6 +1,10 @@
 #![cfg_attr(not(test), no_std)]
 
 // Re-exports
+pub use sanctum_svc_ag_core;
 pub use sanctum_svc_core::*;
 
 pub mod cpi;
+pub mod ix_accs;
+pub mod no_cpi;
+pub mod program;
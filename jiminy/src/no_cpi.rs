@@ -0,0 +1,346 @@
+//! Computing SOL value in-program by reading stake pool accounts directly,
+//! skipping the CPI into the calculator program.
+//!
+//! Unlike the calculator programs, this does not check if the stake pool program
+//! has been upgraded since the calculator was last updated,
+//! so only use this with stake pool programs that are trusted.
+
+use core::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use jiminy_cpi::{
+    account::{Abr, AccountHandle},
+    program_error::ProgramError,
+};
+use sanctum_svc_ag_core::{
+    calc::{SvcCalcAg, SvcCalcAgErr},
+    sanctum_svc_core::traits::SolValCalc,
+    sanctum_svc_lido_core::{
+        self,
+        calc::{LidoCalc, LidoCalcErr},
+        solido_legacy_core::{Lido, LIDO_STATE_ADDR},
+    },
+    sanctum_svc_marinade_core::{
+        self,
+        calc::{MarinadeCalc, MarinadeCalcErr},
+        sanctum_marinade_liquid_staking_core::{State, STATE_PUBKEY},
+    },
+    sanctum_svc_spl_core::{
+        calc::{SplCalc, SplCalcErr},
+        keys::{sanctum_spl, sanctum_spl_multi, spl},
+        sanctum_spl_stake_pool_core::StakePool,
+    },
+    sanctum_svc_wsol_core::{self, calc::WsolCalc},
+    SvcAg,
+};
+
+/// The parts of a stake pool state account required to create its calculator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolStateAcc<'a> {
+    pub key: &'a [u8; 32],
+    pub owner: &'a [u8; 32],
+    pub data: &'a [u8],
+}
+
+/// wSOL has no stake pool state account
+pub type SvcPoolStateAccs<'a> = SvcAg<
+    PoolStateAcc<'a>,
+    PoolStateAcc<'a>,
+    PoolStateAcc<'a>,
+    PoolStateAcc<'a>,
+    PoolStateAcc<'a>,
+    (),
+>;
+
+/// wSOL has no stake pool state account
+pub type SvcPoolStateHandles<'a> = SvcAg<
+    AccountHandle<'a>,
+    AccountHandle<'a>,
+    AccountHandle<'a>,
+    AccountHandle<'a>,
+    AccountHandle<'a>,
+    (),
+>;
+
+/// Creates the calculator for `lst_mint` from its stake pool state account.
+///
+/// Checks that
+/// - `pool_state` is owned by the expected stake pool program
+/// - `pool_state` is the expected account for stake pools with a single known state account
+/// - `pool_state`'s LST mint is `lst_mint`
+///
+/// `current_epoch` should be read from the clock sysvar.
+pub fn svc_calc_ag_of_accs(
+    lst_mint: &[u8; 32],
+    pool_state: &SvcPoolStateAccs<'_>,
+    current_epoch: u64,
+) -> Result<SvcCalcAg, NoCpiErr> {
+    Ok(match pool_state {
+        SvcAg::Lido(acc) => {
+            check_pool_state(
+                acc,
+                Some(&LIDO_STATE_ADDR),
+                &sanctum_svc_lido_core::keys::POOL_PROG_ID,
+            )?;
+            let lido = Lido::borsh_de(acc.data).map_err(|_e| NoCpiErr::AccDeser)?;
+            check_lst_mint(&lido.st_sol_mint, lst_mint)?;
+            SvcAg::Lido(LidoCalc::new(&lido, current_epoch))
+        }
+        SvcAg::Marinade(acc) => {
+            check_pool_state(
+                acc,
+                Some(&STATE_PUBKEY),
+                &sanctum_svc_marinade_core::keys::POOL_PROG_ID,
+            )?;
+            let marinade = State::borsh_de(acc.data).map_err(|_e| NoCpiErr::AccDeser)?;
+            check_lst_mint(&marinade.msol_mint, lst_mint)?;
+            SvcAg::Marinade(MarinadeCalc::new(&marinade))
+        }
+        SvcAg::SanctumSpl(acc) => SvcAg::SanctumSpl(spl_calc(
+            acc,
+            &sanctum_spl::POOL_PROG_ID,
+            lst_mint,
+            current_epoch,
+        )?),
+        SvcAg::SanctumSplMulti(acc) => SvcAg::SanctumSplMulti(spl_calc(
+            acc,
+            &sanctum_spl_multi::POOL_PROG_ID,
+            lst_mint,
+            current_epoch,
+        )?),
+        SvcAg::Spl(acc) => SvcAg::Spl(spl_calc(acc, &spl::POOL_PROG_ID, lst_mint, current_epoch)?),
+        SvcAg::Wsol(()) => {
+            check_lst_mint(&sanctum_svc_wsol_core::LST_MINT, lst_mint)?;
+            SvcAg::Wsol(WsolCalc)
+        }
+    })
+}
+
+/// [`svc_calc_ag_of_accs`] for account handles
+#[inline]
+pub fn svc_calc_ag(
+    abr: &Abr,
+    lst_mint: AccountHandle<'_>,
+    pool_state: &SvcPoolStateHandles<'_>,
+    current_epoch: u64,
+) -> Result<SvcCalcAg, NoCpiErr> {
+    let acc = |h: &AccountHandle<'_>| {
+        let acc = abr.get(*h);
+        PoolStateAcc {
+            key: acc.key(),
+            owner: acc.owner(),
+            data: acc.data(),
+        }
+    };
+    let pool_state = match pool_state {
+        SvcAg::Lido(h) => SvcAg::Lido(acc(h)),
+        SvcAg::Marinade(h) => SvcAg::Marinade(acc(h)),
+        SvcAg::SanctumSpl(h) => SvcAg::SanctumSpl(acc(h)),
+        SvcAg::SanctumSplMulti(h) => SvcAg::SanctumSplMulti(acc(h)),
+        SvcAg::Spl(h) => SvcAg::Spl(acc(h)),
+        SvcAg::Wsol(()) => SvcAg::Wsol(()),
+    };
+    svc_calc_ag_of_accs(abr.get(lst_mint).key(), &pool_state, current_epoch)
+}
+
+/// CPI-free version of [`crate::cpi::cpi_lst_to_sol`]
+#[inline]
+pub fn lst_to_sol(
+    abr: &Abr,
+    lst_mint: AccountHandle<'_>,
+    pool_state: &SvcPoolStateHandles<'_>,
+    current_epoch: u64,
+    lst_amt: u64,
+) -> Result<RangeInclusive<u64>, NoCpiErr> {
+    svc_calc_ag(abr, lst_mint, pool_state, current_epoch)?
+        .lst_to_sol(lst_amt)
+        .map_err(NoCpiErr::Calc)
+}
+
+/// CPI-free version of [`crate::cpi::cpi_sol_to_lst`]
+#[inline]
+pub fn sol_to_lst(
+    abr: &Abr,
+    lst_mint: AccountHandle<'_>,
+    pool_state: &SvcPoolStateHandles<'_>,
+    current_epoch: u64,
+    lamports: u64,
+) -> Result<RangeInclusive<u64>, NoCpiErr> {
+    svc_calc_ag(abr, lst_mint, pool_state, current_epoch)?
+        .sol_to_lst(lamports)
+        .map_err(NoCpiErr::Calc)
+}
+
+fn spl_calc(
+    acc: &PoolStateAcc<'_>,
+    pool_prog: &[u8; 32],
+    lst_mint: &[u8; 32],
+    current_epoch: u64,
+) -> Result<SplCalc, NoCpiErr> {
+    check_pool_state(acc, None, pool_prog)?;
+    let pool = StakePool::borsh_de(acc.data).map_err(|_e| NoCpiErr::AccDeser)?;
+    check_lst_mint(&pool.pool_mint, lst_mint)?;
+    Ok(SplCalc::new(&pool, current_epoch))
+}
+
+fn check_pool_state(
+    acc: &PoolStateAcc<'_>,
+    key: Option<&[u8; 32]>,
+    owner: &[u8; 32],
+) -> Result<(), NoCpiErr> {
+    if key.is_some_and(|k| k != acc.key) {
+        return Err(NoCpiErr::WrongPoolState);
+    }
+    if acc.owner != owner {
+        return Err(NoCpiErr::WrongPoolStateOwner);
+    }
+    Ok(())
+}
+
+fn check_lst_mint(pool_lst_mint: &[u8; 32], lst_mint: &[u8; 32]) -> Result<(), NoCpiErr> {
+    if pool_lst_mint != lst_mint {
+        return Err(NoCpiErr::LstMintMismatch);
+    }
+    Ok(())
+}
+
+/// Base of [`NoCpiErr`] custom program error codes,
+/// to avoid clashing with the calling program's own error codes
+pub const NO_CPI_ERR_BASE: u32 = 0x5356_4400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoCpiErr {
+    /// Stake pool state account is not the expected account
+    WrongPoolState,
+
+    /// Stake pool state account is not owned by the expected stake pool program
+    WrongPoolStateOwner,
+
+    /// Stake pool state account data could not be deserialized
+    AccDeser,
+
+    /// Stake pool's LST mint is not the given LST mint
+    LstMintMismatch,
+
+    Calc(SvcCalcAgErr),
+}
+
+impl NoCpiErr {
+    #[inline]
+    pub const fn err_code(&self) -> NoCpiErrCode {
+        match self {
+            Self::WrongPoolState => NoCpiErrCode::WrongPoolState,
+            Self::WrongPoolStateOwner => NoCpiErrCode::WrongPoolStateOwner,
+            Self::AccDeser => NoCpiErrCode::AccDeser,
+            Self::LstMintMismatch => NoCpiErrCode::LstMintMismatch,
+            Self::Calc(SvcAg::Lido(e)) => match e {
+                LidoCalcErr::Ratio => NoCpiErrCode::LidoRatio,
+                LidoCalcErr::NotUpdated => NoCpiErrCode::LidoNotUpdated,
+            },
+            Self::Calc(SvcAg::Marinade(e)) => match e {
+                MarinadeCalcErr::Ratio => NoCpiErrCode::MarinadeRatio,
+                MarinadeCalcErr::Paused => NoCpiErrCode::MarinadePaused,
+                MarinadeCalcErr::StakeWithdrawDisabled => {
+                    NoCpiErrCode::MarinadeStakeWithdrawDisabled
+                }
+            },
+            Self::Calc(SvcAg::SanctumSpl(e)) => match e {
+                SplCalcErr::Ratio => NoCpiErrCode::SanctumSplRatio,
+                SplCalcErr::NotUpdated => NoCpiErrCode::SanctumSplNotUpdated,
+            },
+            Self::Calc(SvcAg::SanctumSplMulti(e)) => match e {
+                SplCalcErr::Ratio => NoCpiErrCode::SanctumSplMultiRatio,
+                SplCalcErr::NotUpdated => NoCpiErrCode::SanctumSplMultiNotUpdated,
+            },
+            Self::Calc(SvcAg::Spl(e)) => match e {
+                SplCalcErr::Ratio => NoCpiErrCode::SplRatio,
+                SplCalcErr::NotUpdated => NoCpiErrCode::SplNotUpdated,
+            },
+            Self::Calc(SvcAg::Wsol(e)) => match *e {},
+        }
+    }
+
+    #[inline]
+    pub const fn code(&self) -> u32 {
+        self.err_code() as u32
+    }
+}
+
+/// Custom [`ProgramError`] codes of [`NoCpiErr`],
+/// with a distinct code for each calculator's errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum NoCpiErrCode {
+    WrongPoolState = NO_CPI_ERR_BASE,
+    WrongPoolStateOwner,
+    AccDeser,
+    LstMintMismatch,
+
+    LidoRatio,
+    LidoNotUpdated,
+
+    MarinadeRatio,
+    MarinadePaused,
+    MarinadeStakeWithdrawDisabled,
+
+    SanctumSplRatio,
+    SanctumSplNotUpdated,
+
+    SanctumSplMultiRatio,
+    SanctumSplMultiNotUpdated,
+
+    SplRatio,
+    SplNotUpdated,
+}
+
+impl Display for NoCpiErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongPoolState => f.write_str("WrongPoolState"),
+            Self::WrongPoolStateOwner => f.write_str("WrongPoolStateOwner"),
+            Self::AccDeser => f.write_str("AccDeser"),
+            Self::LstMintMismatch => f.write_str("LstMintMismatch"),
+            Self::Calc(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for NoCpiErr {}
+
+impl From<NoCpiErr> for ProgramError {
+    #[inline]
+    fn from(e: NoCpiErr) -> Self {
+        ProgramError::custom(e.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn err_codes_distinct() {
+        let codes = [
+            NoCpiErr::WrongPoolState,
+            NoCpiErr::WrongPoolStateOwner,
+            NoCpiErr::AccDeser,
+            NoCpiErr::LstMintMismatch,
+            NoCpiErr::Calc(SvcAg::Lido(LidoCalcErr::Ratio)),
+            NoCpiErr::Calc(SvcAg::Lido(LidoCalcErr::NotUpdated)),
+            NoCpiErr::Calc(SvcAg::Marinade(MarinadeCalcErr::Ratio)),
+            NoCpiErr::Calc(SvcAg::Marinade(MarinadeCalcErr::Paused)),
+            NoCpiErr::Calc(SvcAg::Marinade(MarinadeCalcErr::StakeWithdrawDisabled)),
+            NoCpiErr::Calc(SvcAg::SanctumSpl(SplCalcErr::Ratio)),
+            NoCpiErr::Calc(SvcAg::SanctumSpl(SplCalcErr::NotUpdated)),
+            NoCpiErr::Calc(SvcAg::SanctumSplMulti(SplCalcErr::Ratio)),
+            NoCpiErr::Calc(SvcAg::SanctumSplMulti(SplCalcErr::NotUpdated)),
+            NoCpiErr::Calc(SvcAg::Spl(SplCalcErr::Ratio)),
+            NoCpiErr::Calc(SvcAg::Spl(SplCalcErr::NotUpdated)),
+        ]
+        .map(|e| e.code());
+        codes.iter().enumerate().for_each(|(i, c)| {
+            assert!(!codes[..i].contains(c));
+        });
+        assert_eq!(codes[0], NO_CPI_ERR_BASE);
+    }
+}
@@ -0,0 +1,305 @@
+//! Kit for implementing SolValCalc onchain programs.
+//!
+//! A program only needs to implement [`SvcProgram`]
+//! and call [`process_svc_ix`] from its entrypoint, e.g.
+//!
+//! ```ignore
+//! jiminy_entrypoint::program_entrypoint!(process_ix, MAX_ACCS);
+//!
+//! fn process_ix(
+//!     abr: &mut Abr,
+//!     accounts: &[AccountHandle],
+//!     data: &[u8],
+//!     _prog_id: &[u8; 32],
+//! ) -> Result<(), ProgramError> {
+//!     process_svc_ix(&MyProgram, abr, accounts, data)
+//! }
+//! ```
+//!
+//! [`process_svc_ix`] does not depend on the entrypoint,
+//! so the resulting program can be built with `cargo-build-sbf`
+//! and run in an in-process SVM like `mollusk-svm` the same way
+//! as any other calculator program.
+
+use core::{error::Error, fmt::Display};
+
+use jiminy_cpi::{
+    account::{Abr, AccountHandle},
+    program_error::{ProgramError, INVALID_INSTRUCTION_DATA, NOT_ENOUGH_ACCOUNT_KEYS},
+};
+use jiminy_return_data::set_return_data;
+use sanctum_svc_core::{
+    instructions::{
+        return_data::{encode_return_data, RETURN_DATA_LEN},
+        SvcIxData, IX_PRE_ACCS_LEN,
+    },
+    traits::{SolValCalc, SolValCalcAccs},
+};
+
+/// A SolValCalc program: a calculator type plus an account parser
+pub trait SvcProgram {
+    type Calc: SolValCalc;
+    type Accs: SolValCalcAccs<KeysOwned: AsRef<[[u8; 32]]>>;
+
+    /// The accounts suffix expected for `lst_mint`.
+    ///
+    /// Should error if this program does not support `lst_mint`.
+    fn calc_accs(&self, lst_mint: &[u8; 32]) -> Result<Self::Accs, ProgramError>;
+
+    /// Create the calculator from account data.
+    ///
+    /// `suf` is exactly as long as [`Self::calc_accs`]'s suffix
+    /// and its keys have already been checked against it.
+    fn calc(
+        &self,
+        abr: &Abr,
+        lst_mint: AccountHandle<'_>,
+        suf: &[AccountHandle<'_>],
+    ) -> Result<Self::Calc, ProgramError>;
+
+    fn calc_err(&self, e: <Self::Calc as SolValCalc>::Error) -> ProgramError;
+}
+
+/// Processes a `LstToSol` or `SolToLst` instruction:
+/// 1. decode instruction data
+/// 2. check accounts suffix keys against [`SvcProgram::calc_accs`]
+/// 3. create the calculator with [`SvcProgram::calc`]
+/// 4. run it and set its output range as return data
+///
+/// Accounts after the expected suffix are ignored.
+/// A suffix account that is not the expected account fails with [`SvcProgramErr::WrongSufAcc`].
+pub fn process_svc_ix<P: SvcProgram>(
+    prog: &P,
+    abr: &Abr,
+    accounts: &[AccountHandle<'_>],
+    data: &[u8],
+) -> Result<(), ProgramError> {
+    let ix_data = svc_ix_data(data)?;
+
+    let (pre, suf) = accounts
+        .split_first_chunk::<IX_PRE_ACCS_LEN>()
+        .ok_or(NOT_ENOUGH_ACCOUNT_KEYS)?;
+    let [lst_mint] = *pre;
+
+    let expected = prog.calc_accs(abr.get(lst_mint).key())?.suf_keys_owned();
+    let expected = expected.as_ref();
+    let suf = suf.get(..expected.len()).ok_or(NOT_ENOUGH_ACCOUNT_KEYS)?;
+    if suf
+        .iter()
+        .zip(expected)
+        .any(|(h, expected)| abr.get(*h).key() != expected)
+    {
+        return Err(SvcProgramErr::WrongSufAcc.into());
+    }
+
+    let calc = prog.calc(abr, lst_mint, suf)?;
+    let return_data = svc_ix_return_data(&calc, ix_data, |e| prog.calc_err(e))?;
+    set_return_data(&return_data);
+    Ok(())
+}
+
+/// Errors with [`INVALID_INSTRUCTION_DATA`] if `data` is not
+/// `LstToSol` or `SolToLst` instruction data
+#[inline]
+pub fn svc_ix_data(data: &[u8]) -> Result<SvcIxData, ProgramError> {
+    SvcIxData::try_from_buf(data).map_err(|_e| INVALID_INSTRUCTION_DATA)
+}
+
+/// Runs `calc` for `ix_data` and encodes its output range
+/// as the return data [`process_svc_ix`] sets
+#[inline]
+pub fn svc_ix_return_data<C: SolValCalc>(
+    calc: &C,
+    ix_data: SvcIxData,
+    calc_err: impl FnOnce(C::Error) -> ProgramError,
+) -> Result<[u8; RETURN_DATA_LEN], ProgramError> {
+    let range = match ix_data {
+        SvcIxData::LstToSol(d) => calc.lst_to_sol(d.amt()),
+        SvcIxData::SolToLst(d) => calc.sol_to_lst(d.amt()),
+    }
+    .map_err(calc_err)?;
+
+    // SolValCalc impls should always return min <= max
+    encode_return_data(&range).map_err(|_e| SvcProgramErr::CalcMinGtMax.into())
+}
+
+/// Base of [`SvcProgramErr`] custom program error codes,
+/// to avoid clashing with the program's own error codes
+pub const SVC_PROGRAM_ERR_BASE: u32 = 0x5356_4500;
+
+/// Custom [`ProgramError`]s returned by [`process_svc_ix`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum SvcProgramErr {
+    /// An accounts suffix key is not the expected account
+    WrongSufAcc = SVC_PROGRAM_ERR_BASE,
+
+    /// Calculator returned a range with start greater than end
+    CalcMinGtMax,
+}
+
+impl SvcProgramErr {
+    #[inline]
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Display for SvcProgramErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::WrongSufAcc => "WrongSufAcc",
+            Self::CalcMinGtMax => "CalcMinGtMax",
+        })
+    }
+}
+
+impl Error for SvcProgramErr {}
+
+impl From<SvcProgramErr> for ProgramError {
+    #[inline]
+    fn from(e: SvcProgramErr) -> Self {
+        ProgramError::custom(e.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::RangeInclusive;
+
+    use sanctum_svc_ag_core::sanctum_svc_wsol_core::calc::WsolCalc;
+    use sanctum_svc_core::instructions::{
+        lst_to_sol::LstToSolIxData, sol_to_lst::SolToLstIxData, IX_DATA_LEN,
+    };
+
+    use super::*;
+
+    /// Fixed output ranges, or an error if `fail`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct MockCalc {
+        lst_to_sol: RangeInclusive<u64>,
+        sol_to_lst: RangeInclusive<u64>,
+        fail: bool,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct MockErr;
+
+    impl core::fmt::Display for MockErr {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("MockErr")
+        }
+    }
+
+    impl core::error::Error for MockErr {}
+
+    impl SolValCalc for MockCalc {
+        type Error = MockErr;
+
+        fn lst_to_sol(&self, _lst_amount: u64) -> Result<RangeInclusive<u64>, Self::Error> {
+            if self.fail {
+                return Err(MockErr);
+            }
+            Ok(self.lst_to_sol.clone())
+        }
+
+        fn sol_to_lst(&self, _lamports_amount: u64) -> Result<RangeInclusive<u64>, Self::Error> {
+            if self.fail {
+                return Err(MockErr);
+            }
+            Ok(self.sol_to_lst.clone())
+        }
+    }
+
+    const MOCK: MockCalc = MockCalc {
+        lst_to_sol: 1..=2,
+        sol_to_lst: 3..=4,
+        fail: false,
+    };
+
+    fn ix_data(buf: &[u8]) -> SvcIxData {
+        svc_ix_data(buf).unwrap()
+    }
+
+    fn mock_calc_err(_e: MockErr) -> ProgramError {
+        ProgramError::custom(1)
+    }
+
+    #[test]
+    fn err_codes_distinct() {
+        assert_ne!(
+            SvcProgramErr::WrongSufAcc.code(),
+            SvcProgramErr::CalcMinGtMax.code()
+        );
+        assert_ne!(
+            ProgramError::from(SvcProgramErr::WrongSufAcc),
+            mock_calc_err(MockErr)
+        );
+    }
+
+    #[test]
+    fn lst_to_sol_return_data() {
+        let data = ix_data(LstToSolIxData::new(5).as_buf());
+        assert_eq!(
+            svc_ix_return_data(&WsolCalc, data, |e| match e {}),
+            Ok(encode_return_data(&(5..=5)).unwrap())
+        );
+        assert_eq!(
+            svc_ix_return_data(&MOCK, data, mock_calc_err),
+            Ok(encode_return_data(&MOCK.lst_to_sol).unwrap())
+        );
+    }
+
+    #[test]
+    fn sol_to_lst_return_data() {
+        let data = ix_data(SolToLstIxData::new(u64::MAX).as_buf());
+        assert_eq!(
+            svc_ix_return_data(&WsolCalc, data, |e| match e {}),
+            Ok(encode_return_data(&(u64::MAX..=u64::MAX)).unwrap())
+        );
+        assert_eq!(
+            svc_ix_return_data(&MOCK, data, mock_calc_err),
+            Ok(encode_return_data(&MOCK.sol_to_lst).unwrap())
+        );
+    }
+
+    #[test]
+    fn calc_err_mapped() {
+        let calc = MockCalc { fail: true, ..MOCK };
+        let data = ix_data(LstToSolIxData::new(1).as_buf());
+        assert_eq!(
+            svc_ix_return_data(&calc, data, mock_calc_err),
+            Err(mock_calc_err(MockErr))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn min_gt_max_err() {
+        let calc = MockCalc {
+            lst_to_sol: 2..=1,
+            ..MOCK
+        };
+        let data = ix_data(LstToSolIxData::new(1).as_buf());
+        assert_eq!(
+            svc_ix_return_data(&calc, data, mock_calc_err),
+            Err(SvcProgramErr::CalcMinGtMax.into())
+        );
+    }
+
+    #[test]
+    fn bad_ix_data() {
+        let valid = *LstToSolIxData::new(1).as_buf();
+        let mut unknown_discm = valid;
+        unknown_discm[0] = u8::MAX;
+        [
+            &[][..],
+            &valid[..IX_DATA_LEN - 1],
+            &[valid.as_slice(), &[0]].concat(),
+            &unknown_discm,
+        ]
+        .into_iter()
+        .for_each(|d| assert_eq!(svc_ix_data(d), Err(INVALID_INSTRUCTION_DATA)));
+    }
+}
@@ -1,6 +1,6 @@
 use core::{error::Error, fmt::Display, ops::RangeInclusive};
 
-use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios};
 use sanctum_token_ratio_compat::floor_ratio_u64_u64_reverse;
 use solido_legacy_core::{ExchangeRate, Lido};
 
@@ -64,6 +64,29 @@ impl LidoCalc {
     }
 }
 
+/// SolValCalcRatios
+impl LidoCalc {
+    /// Lido does not charge any fees, so `pre_fee` = `post_fee`
+    #[inline]
+    pub const fn svc_lst_to_sol_ratios(&self) -> Result<LstToSolRatios, LidoCalcErr> {
+        if !self.is_updated() {
+            return Err(LidoCalcErr::NotUpdated);
+        }
+        Ok(LstToSolRatios::no_fee(
+            self.exchange_rate.sol_balance_over_st_sol_supply().0,
+        ))
+    }
+}
+
+impl SolValCalcRatios for LidoCalc {
+    type Error = LidoCalcErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.svc_lst_to_sol_ratios()
+    }
+}
+
 impl SolValCalc for LidoCalc {
     type Error = LidoCalcErr;
 
@@ -20,6 +20,12 @@ id_str!(
     "CHZNLhDXKrsXBmmv947RFciquwBsn2NdABmhpxoX3wgZ"
 );
 
+id_str!(
+    LST_MINT_STR,
+    LST_MINT,
+    "7dHbWXmci3dT8UFYWYZweBLXgycu7Y3iL6trKn1Y7ARj"
+);
+
 const STATE: ([u8; 32], u8) = const_find_state(&crate::ID);
 pub const STATE_ID: [u8; 32] = STATE.0;
 pub const STATE_BUMP: u8 = STATE.1;
@@ -9,4 +9,7 @@ pub mod calc;
 pub mod instructions;
 pub mod keys;
 
+#[cfg(feature = "update")]
+pub mod update;
+
 keys::id_str!(ID_STR, ID, "1idUSy4MGGKyKhvjSnGZ6Zc7Q4eKQcibym4BkEEw9KR");
@@ -0,0 +1,116 @@
+use core::{
+    error::Error,
+    fmt::{Display, Formatter},
+    iter::Chain,
+};
+
+use sanctum_svc_generic::{
+    instructions::IxSufKeysOwned,
+    upgrade::{self, UpgradeCheck},
+};
+use solido_legacy_core::{Lido, LIDO_STATE_ADDR, SYSVAR_CLOCK};
+
+use crate::{calc::LidoCalc, instructions::sol_val_calc::IX_SUF_KEYS_OWNED, keys::LST_MINT};
+
+// Re-exports
+pub use sanctum_svc_core::update::*;
+
+pub type PkIter = Chain<core::array::IntoIter<[u8; 32], 2>, UpgradeCheckPkIter>;
+
+#[inline]
+pub fn accounts_to_update(upgrade_check_enabled: bool) -> PkIter {
+    [LIDO_STATE_ADDR, SYSVAR_CLOCK]
+        .into_iter()
+        .chain(upgrade_check_pk_iter(
+            upgrade_check_enabled,
+            *IX_SUF_KEYS_OWNED.state(),
+            *IX_SUF_KEYS_OWNED.pool_progdata(),
+        ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LidoUpdateErr {
+    AccDeser {
+        pk: [u8; 32],
+    },
+
+    /// Lido state's `st_sol_mint` does not match [`LST_MINT`]
+    LstMintMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl Display for LidoUpdateErr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AccDeser { .. } => f.write_str("AccDeser"),
+            Self::LstMintMismatch { .. } => f.write_str("LstMintMismatch"),
+        }
+    }
+}
+
+impl Error for LidoUpdateErr {}
+
+#[inline]
+pub fn updated_calc(update_map: impl UpdateMap) -> Result<LidoCalc, UpdateErr<LidoUpdateErr>> {
+    let lido = fetched_lido(&update_map)?;
+    let current_epoch = fetched_current_epoch(&update_map)?;
+    Ok(LidoCalc::new(&lido, current_epoch))
+}
+
+/// [`updated_calc`], but exclude data derived from clock
+/// (currently just `current_epoch`), which is retained from `calc`
+/// if it exists, otherwise set to default.
+#[inline]
+pub fn updated_calc_no_clock(
+    calc: Option<&LidoCalc>,
+    update_map: impl UpdateMap,
+) -> Result<LidoCalc, UpdateErr<LidoUpdateErr>> {
+    let lido = fetched_lido(&update_map)?;
+    let current_epoch = calc.map(|c| c.current_epoch).unwrap_or_default();
+    Ok(LidoCalc::new(&lido, current_epoch))
+}
+
+/// [`upgrade::updated_upgrade_check`] for the accounts suffix `suf`
+#[inline]
+pub fn updated_upgrade_check(
+    upgrade_check: UpgradeCheck,
+    suf: &IxSufKeysOwned,
+    update_map: impl UpdateMap,
+) -> Result<UpgradeCheck, UpdateErr<LidoUpdateErr>> {
+    upgrade::updated_upgrade_check(
+        upgrade_check,
+        suf.state(),
+        suf.pool_progdata(),
+        update_map,
+        |pk| LidoUpdateErr::AccDeser { pk },
+    )
+}
+
+/// Errors if the lido state's `st_sol_mint` is not [`LST_MINT`]
+#[inline]
+pub fn fetched_lido(update_map: impl UpdateMap) -> Result<Lido, UpdateErr<LidoUpdateErr>> {
+    let lido_acc = update_map.get_account_checked(&LIDO_STATE_ADDR)?;
+    let lido = Lido::borsh_de(lido_acc.data()).map_err(|_e| {
+        UpdateErr::Inner(LidoUpdateErr::AccDeser {
+            pk: LIDO_STATE_ADDR,
+        })
+    })?;
+    if lido.st_sol_mint != LST_MINT {
+        return Err(UpdateErr::Inner(LidoUpdateErr::LstMintMismatch {
+            expected: LST_MINT,
+            actual: lido.st_sol_mint,
+        }));
+    }
+    Ok(lido)
+}
+
+#[inline]
+pub fn fetched_current_epoch(update_map: impl UpdateMap) -> Result<u64, UpdateErr<LidoUpdateErr>> {
+    let clock_acc = update_map.get_account_checked(&SYSVAR_CLOCK)?;
+    epoch_from_clock_data(clock_acc.data()).ok_or(UpdateErr::Inner(LidoUpdateErr::AccDeser {
+        pk: SYSVAR_CLOCK,
+    }))
+}
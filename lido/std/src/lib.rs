@@ -1,4 +1,13 @@
-use sanctum_svc_lido_core::{calc::LidoCalc, instructions::sol_val_calc::LidoCalcAccs};
+use sanctum_svc_lido_core::{
+    calc::LidoCalc,
+    instructions::sol_val_calc::LidoCalcAccs,
+    keys::LST_MINT,
+    sanctum_svc_core::instructions::svc_ix::{SvcIx, SvcIxTy},
+    sanctum_svc_generic::{
+        instructions::{IxSufAccFlags, IxSufKeysOwned},
+        upgrade::{PoolProgUpgradedErr, UpgradeCheck},
+    },
+};
 
 // Re-exports
 pub use sanctum_svc_lido_core::*;
@@ -10,6 +19,9 @@ pub struct LidoSvcStd {
     /// Might be `None` at initialization before accounts required
     /// to create the calc have been fetched
     pub calc: Option<LidoCalc>,
+
+    /// Disabled by default, see [`Self::with_upgrade_check`]
+    pub upgrade_check: UpgradeCheck,
 }
 
 impl Default for LidoSvcStd {
@@ -21,7 +33,25 @@ impl Default for LidoSvcStd {
 
 /// Constructors
 impl LidoSvcStd {
-    pub const DEFAULT: Self = Self { calc: None };
+    pub const DEFAULT: Self = Self {
+        calc: None,
+        upgrade_check: UpgradeCheck::Disabled,
+    };
+
+    /// Opt-in to detecting stake pool program upgrades.
+    ///
+    /// This adds the calculator program state and stake pool program data accounts
+    /// to the accounts to update with.
+    ///
+    /// Upgrades are only detected through [`Self::try_as_calc`],
+    /// [`Self::as_calc`] is unchecked.
+    #[inline]
+    pub const fn with_upgrade_check(mut self) -> Self {
+        if !self.upgrade_check.is_enabled() {
+            self.upgrade_check = UpgradeCheck::Enabled(None);
+        }
+        self
+    }
 }
 
 /// Accessors
@@ -31,8 +61,37 @@ impl LidoSvcStd {
         self.calc.as_ref()
     }
 
+    /// [`Self::as_calc`], but errors if upgrade detection is enabled
+    /// and the stake pool program has been upgraded
+    #[inline]
+    pub const fn try_as_calc(&self) -> Result<Option<&LidoCalc>, PoolProgUpgradedErr> {
+        match self.upgrade_check.check() {
+            Ok(()) => Ok(self.as_calc()),
+            Err(e) => Err(e),
+        }
+    }
+
     #[inline]
     pub const fn as_accs(&self) -> &LidoCalcAccs {
         &LidoCalcAccs
     }
+
+    /// Updates fail if the fetched pool state's LST mint does not match this
+    #[inline]
+    pub const fn lst_mint(&self) -> &[u8; 32] {
+        &LST_MINT
+    }
+}
+
+/// Instructions
+impl LidoSvcStd {
+    /// Complete SolValCalc interface instruction for [`Self::lst_mint`].
+    ///
+    /// `amt` is LST amount for [`SvcIxTy::LstToSol`]
+    /// and lamport amount for [`SvcIxTy::SolToLst`].
+    #[inline]
+    pub fn svc_ix(&self, ty: SvcIxTy, amt: u64) -> SvcIx<IxSufKeysOwned, IxSufAccFlags> {
+        let accs = self.as_accs();
+        SvcIx::new(*accs.svc_program_id(), *self.lst_mint(), accs, ty, amt)
+    }
 }
@@ -1,7 +1,7 @@
 use core::{error::Error, fmt::Display, ops::RangeInclusive};
 
 use sanctum_marinade_liquid_staking_core::{FeeCents, StakeSystem, State, ValidatorSystem};
-use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios};
 use sanctum_token_ratio_compat::{
     fee_floor_ratio_u32_u32_reverse_from_rem, floor_ratio_u64_u64_reverse,
 };
@@ -164,6 +164,37 @@ impl MarinadeCalc {
     }
 }
 
+/// SolValCalcRatios
+impl MarinadeCalc {
+    #[inline]
+    pub const fn svc_lst_to_sol_ratios(&self) -> Result<LstToSolRatios, MarinadeCalcErr> {
+        if let Err(e) = self.can_withdraw_stake() {
+            return Err(e);
+        }
+        let fee = match self.withdraw_stake_account_fee().to_fee_floor() {
+            Some(f) => f,
+            None => return Err(MarinadeCalcErr::Ratio),
+        };
+        let Ratio { n, d } = fee.one_minus_fee_ratio();
+        Ok(LstToSolRatios::with_one_minus_fee(
+            self.lamports_over_supply().0,
+            Ratio {
+                n: n as u64,
+                d: d as u64,
+            },
+        ))
+    }
+}
+
+impl SolValCalcRatios for MarinadeCalc {
+    type Error = MarinadeCalcErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.svc_lst_to_sol_ratios()
+    }
+}
+
 impl SolValCalc for MarinadeCalc {
     type Error = MarinadeCalcErr;
 
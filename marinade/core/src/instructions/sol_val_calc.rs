@@ -42,6 +42,12 @@ impl SolValCalcAccs for MarinadeCalcAccs {
 }
 
 impl MarinadeCalcAccs {
+    /// The calculator program's program ID
+    #[inline]
+    pub const fn svc_program_id(&self) -> &[u8; 32] {
+        &crate::ID
+    }
+
     #[inline]
     pub const fn svc_suf_keys_owned(&self) -> IxSufKeysOwned {
         IX_SUF_KEYS_OWNED
@@ -20,6 +20,12 @@ id_str!(
     "4PQH9YmfuKrVyZaibkLYpJZPv2FPaybhq2GAuBcWMSBf"
 );
 
+id_str!(
+    LST_MINT_STR,
+    LST_MINT,
+    "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So"
+);
+
 const STATE: ([u8; 32], u8) = const_find_state(&crate::ID);
 pub const STATE_ID: [u8; 32] = STATE.0;
 pub const STATE_BUMP: u8 = STATE.1;
@@ -9,4 +9,7 @@ pub mod calc;
 pub mod instructions;
 pub mod keys;
 
+#[cfg(feature = "update")]
+pub mod update;
+
 keys::id_str!(ID_STR, ID, "mare3SCyfZkAndpBRBeonETmkCCB3TJTTrz8ZN2dnhP");
@@ -0,0 +1,85 @@
+use core::{
+    error::Error,
+    fmt::{Display, Formatter},
+    iter::{once, Chain, Once},
+};
+
+use sanctum_marinade_liquid_staking_core::{State, STATE_PUBKEY};
+use sanctum_svc_generic::{
+    instructions::IxSufKeysOwned,
+    upgrade::{self, UpgradeCheck},
+};
+
+use crate::{calc::MarinadeCalc, instructions::sol_val_calc::IX_SUF_KEYS_OWNED, keys::LST_MINT};
+
+// Re-exports
+pub use sanctum_svc_core::update::*;
+
+pub type PkIter = Chain<Once<[u8; 32]>, UpgradeCheckPkIter>;
+
+#[inline]
+pub fn accounts_to_update(upgrade_check_enabled: bool) -> PkIter {
+    once(STATE_PUBKEY).chain(upgrade_check_pk_iter(
+        upgrade_check_enabled,
+        *IX_SUF_KEYS_OWNED.state(),
+        *IX_SUF_KEYS_OWNED.pool_progdata(),
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarinadeUpdateErr {
+    AccDeser {
+        pk: [u8; 32],
+    },
+
+    /// Marinade state's `msol_mint` does not match [`LST_MINT`]
+    LstMintMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl Display for MarinadeUpdateErr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AccDeser { .. } => f.write_str("AccDeser"),
+            Self::LstMintMismatch { .. } => f.write_str("LstMintMismatch"),
+        }
+    }
+}
+
+impl Error for MarinadeUpdateErr {}
+
+/// Errors if the marinade state's `msol_mint` is not [`LST_MINT`]
+#[inline]
+pub fn updated_calc(
+    update_map: impl UpdateMap,
+) -> Result<MarinadeCalc, UpdateErr<MarinadeUpdateErr>> {
+    let marinade_acc = update_map.get_account_checked(&STATE_PUBKEY)?;
+    let marinade = State::borsh_de(marinade_acc.data())
+        .map_err(|_e| UpdateErr::Inner(MarinadeUpdateErr::AccDeser { pk: STATE_PUBKEY }))?;
+    if marinade.msol_mint != LST_MINT {
+        return Err(UpdateErr::Inner(MarinadeUpdateErr::LstMintMismatch {
+            expected: LST_MINT,
+            actual: marinade.msol_mint,
+        }));
+    }
+    Ok(MarinadeCalc::new(&marinade))
+}
+
+/// [`upgrade::updated_upgrade_check`] for the accounts suffix `suf`
+#[inline]
+pub fn updated_upgrade_check(
+    upgrade_check: UpgradeCheck,
+    suf: &IxSufKeysOwned,
+    update_map: impl UpdateMap,
+) -> Result<UpgradeCheck, UpdateErr<MarinadeUpdateErr>> {
+    upgrade::updated_upgrade_check(
+        upgrade_check,
+        suf.state(),
+        suf.pool_progdata(),
+        update_map,
+        |pk| MarinadeUpdateErr::AccDeser { pk },
+    )
+}
@@ -2,7 +2,7 @@ use core::{error::Error, fmt::Display, ops::RangeInclusive};
 
 use sanctum_fee_ratio::ratio::{Ceil, Ratio};
 use sanctum_spl_stake_pool_core::{Fee, StakePool};
-use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios};
 use sanctum_token_ratio_compat::{
     fee_ceil_ratio_u64_u64_reverse_from_rem, floor_ratio_u64_u64_reverse,
 };
@@ -129,6 +129,39 @@ impl SplCalc {
     }
 }
 
+/// SolValCalcRatios
+impl SplCalc {
+    #[inline]
+    pub const fn svc_lst_to_sol_ratios(&self) -> Result<LstToSolRatios, SplCalcErr> {
+        if !self.is_updated() {
+            return Err(SplCalcErr::NotUpdated);
+        }
+        let fee = match self.stake_withdrawal_fee_ceil() {
+            Some(f) => f,
+            None => return Err(SplCalcErr::Ratio),
+        };
+        // 0-denominator fees are already 0 fees in `stake_withdrawal_fee_ceil`,
+        // normalize any remaining 0-denominator ratio to 1/1 to match `svc_lst_to_sol`
+        let one_minus_fee = match fee.one_minus_fee_ratio() {
+            Ratio { d: 0, .. } => Ratio { n: 1, d: 1 },
+            r => r,
+        };
+        Ok(LstToSolRatios::with_one_minus_fee(
+            self.lst_to_lamports_ratio().0,
+            one_minus_fee,
+        ))
+    }
+}
+
+impl SolValCalcRatios for SplCalc {
+    type Error = SplCalcErr;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        self.svc_lst_to_sol_ratios()
+    }
+}
+
 impl SolValCalc for SplCalc {
     type Error = SplCalcErr;
 
@@ -25,6 +25,12 @@ macro_rules! calc_accs {
 
         /// SolValCalcAccs
         impl $Ty {
+            /// The calculator program's program ID
+            #[inline]
+            pub const fn svc_program_id(&self) -> &[u8; 32] {
+                &crate::keys::$progmod::ID
+            }
+
             const BASE_KEYS_OWNED: IxSufKeysOwned = IxSufKeysOwned::memset([0u8; 32])
                 .const_with_pool_prog(crate::keys::$progmod::POOL_PROG_ID)
                 .const_with_pool_progdata(crate::keys::$progmod::POOL_PROGDATA_ID)
@@ -8,3 +8,6 @@ pub use sanctum_svc_generic;
 pub mod calc;
 pub mod instructions;
 pub mod keys;
+
+#[cfg(feature = "update")]
+pub mod update;
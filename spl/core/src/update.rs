@@ -0,0 +1,135 @@
+use core::{
+    error::Error,
+    fmt::{Display, Formatter},
+    iter::Chain,
+};
+
+use sanctum_spl_stake_pool_core::{StakePool, SYSVAR_CLOCK};
+use sanctum_svc_generic::{
+    instructions::IxSufKeysOwned,
+    upgrade::{self, UpgradeCheck},
+};
+
+use crate::calc::SplCalc;
+
+// Re-exports
+pub use sanctum_svc_core::update::*;
+
+pub type PkIter = Chain<core::array::IntoIter<[u8; 32], 2>, UpgradeCheckPkIter>;
+
+#[inline]
+pub fn accounts_to_update(
+    stake_pool_addr: [u8; 32],
+    suf: &IxSufKeysOwned,
+    upgrade_check_enabled: bool,
+) -> PkIter {
+    [stake_pool_addr, SYSVAR_CLOCK]
+        .into_iter()
+        .chain(upgrade_check_pk_iter(
+            upgrade_check_enabled,
+            *suf.state(),
+            *suf.pool_progdata(),
+        ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplUpdateErr {
+    AccDeser {
+        pk: [u8; 32],
+    },
+
+    /// Stake pool's `pool_mint` does not match the LST mint the calc was bound to
+    LstMintMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl Display for SplUpdateErr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AccDeser { .. } => f.write_str("AccDeser"),
+            Self::LstMintMismatch { .. } => f.write_str("LstMintMismatch"),
+        }
+    }
+}
+
+impl Error for SplUpdateErr {}
+
+/// Returns `(calc, pool_mint)`.
+///
+/// Errors if `lst_mint` is `Some` and does not match the pool's `pool_mint`
+#[inline]
+pub fn updated_calc(
+    stake_pool_addr: [u8; 32],
+    lst_mint: Option<[u8; 32]>,
+    update_map: impl UpdateMap,
+) -> Result<(SplCalc, [u8; 32]), UpdateErr<SplUpdateErr>> {
+    let pool = fetched_stake_pool(&stake_pool_addr, lst_mint, &update_map)?;
+    let current_epoch = fetched_current_epoch(&update_map)?;
+    Ok((SplCalc::new(&pool, current_epoch), pool.pool_mint))
+}
+
+/// [`updated_calc`], but exclude data derived from clock
+/// (currently just `current_epoch`), which is retained from `calc`
+/// if it exists, otherwise set to default.
+#[inline]
+pub fn updated_calc_no_clock(
+    stake_pool_addr: [u8; 32],
+    lst_mint: Option<[u8; 32]>,
+    calc: Option<&SplCalc>,
+    update_map: impl UpdateMap,
+) -> Result<(SplCalc, [u8; 32]), UpdateErr<SplUpdateErr>> {
+    let pool = fetched_stake_pool(&stake_pool_addr, lst_mint, &update_map)?;
+    let current_epoch = calc.map(|c| c.current_epoch).unwrap_or_default();
+    Ok((SplCalc::new(&pool, current_epoch), pool.pool_mint))
+}
+
+/// [`upgrade::updated_upgrade_check`] for the accounts suffix `suf`
+#[inline]
+pub fn updated_upgrade_check(
+    upgrade_check: UpgradeCheck,
+    suf: &IxSufKeysOwned,
+    update_map: impl UpdateMap,
+) -> Result<UpgradeCheck, UpdateErr<SplUpdateErr>> {
+    upgrade::updated_upgrade_check(
+        upgrade_check,
+        suf.state(),
+        suf.pool_progdata(),
+        update_map,
+        |pk| SplUpdateErr::AccDeser { pk },
+    )
+}
+
+/// Errors if `lst_mint` is `Some` and does not match the pool's `pool_mint`
+#[inline]
+pub fn fetched_stake_pool(
+    stake_pool_addr: &[u8; 32],
+    lst_mint: Option<[u8; 32]>,
+    update_map: impl UpdateMap,
+) -> Result<StakePool, UpdateErr<SplUpdateErr>> {
+    let pool_acc = update_map.get_account_checked(stake_pool_addr)?;
+    let pool = StakePool::borsh_de(pool_acc.data()).map_err(|_e| {
+        UpdateErr::Inner(SplUpdateErr::AccDeser {
+            pk: *stake_pool_addr,
+        })
+    })?;
+    match lst_mint {
+        Some(expected) if expected != pool.pool_mint => {
+            Err(UpdateErr::Inner(SplUpdateErr::LstMintMismatch {
+                expected,
+                actual: pool.pool_mint,
+            }))
+        }
+        _ => Ok(pool),
+    }
+}
+
+#[inline]
+pub fn fetched_current_epoch(update_map: impl UpdateMap) -> Result<u64, UpdateErr<SplUpdateErr>> {
+    let clock_acc = update_map.get_account_checked(&SYSVAR_CLOCK)?;
+    epoch_from_clock_data(clock_acc.data()).ok_or(UpdateErr::Inner(SplUpdateErr::AccDeser {
+        pk: SYSVAR_CLOCK,
+    }))
+}
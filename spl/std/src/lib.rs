@@ -1,6 +1,11 @@
 use sanctum_svc_spl_core::{
     calc::SplCalc,
     instructions::sol_val_calc::{SanctumSplCalcAccs, SanctumSplMultiCalcAccs, SplCalcAccs},
+    sanctum_svc_core::instructions::svc_ix::{SvcIx, SvcIxTy},
+    sanctum_svc_generic::{
+        instructions::{IxSufAccFlags, IxSufKeysOwned},
+        upgrade::{PoolProgUpgradedErr, UpgradeCheck},
+    },
 };
 
 // Re-exports
@@ -18,6 +23,16 @@ pub struct GenSplSvcStd<A> {
     /// to create the calc have been fetched
     pub calc: Option<SplCalc>,
     pub accs: A,
+
+    /// The stake pool's `pool_mint`.
+    ///
+    /// If `None`, bound to the stake pool's `pool_mint` on first update.
+    /// Subsequent updates fail if the stake pool's `pool_mint` does not match.
+    /// See [`Self::with_lst_mint`].
+    pub lst_mint: Option<[u8; 32]>,
+
+    /// Disabled by default, see [`Self::with_upgrade_check`]
+    pub upgrade_check: UpgradeCheck,
 }
 
 /// Constructors
@@ -26,6 +41,8 @@ impl SanctumSplSvcStd {
         Self {
             calc: None,
             accs: SanctumSplCalcAccs { stake_pool_addr },
+            lst_mint: None,
+            upgrade_check: UpgradeCheck::Disabled,
         }
     }
 }
@@ -36,6 +53,8 @@ impl SanctumSplMultiSvcStd {
         Self {
             calc: None,
             accs: SanctumSplMultiCalcAccs { stake_pool_addr },
+            lst_mint: None,
+            upgrade_check: UpgradeCheck::Disabled,
         }
     }
 }
@@ -46,7 +65,35 @@ impl SplSvcStd {
         Self {
             calc: None,
             accs: SplCalcAccs { stake_pool_addr },
+            lst_mint: None,
+            upgrade_check: UpgradeCheck::Disabled,
+        }
+    }
+}
+
+/// Constructors
+impl<A> GenSplSvcStd<A> {
+    /// Bind this calc to `lst_mint` so that updating with a stake pool
+    /// that has a different `pool_mint` fails
+    #[inline]
+    pub const fn with_lst_mint(mut self, lst_mint: [u8; 32]) -> Self {
+        self.lst_mint = Some(lst_mint);
+        self
+    }
+
+    /// Opt-in to detecting stake pool program upgrades.
+    ///
+    /// This adds the calculator program state and stake pool program data accounts
+    /// to the accounts to update with.
+    ///
+    /// Upgrades are only detected through [`Self::try_as_calc`],
+    /// [`Self::as_calc`] is unchecked.
+    #[inline]
+    pub const fn with_upgrade_check(mut self) -> Self {
+        if !self.upgrade_check.is_enabled() {
+            self.upgrade_check = UpgradeCheck::Enabled(None);
         }
+        self
     }
 }
 
@@ -57,8 +104,51 @@ impl<A> GenSplSvcStd<A> {
         self.calc.as_ref()
     }
 
+    /// [`Self::as_calc`], but errors if upgrade detection is enabled
+    /// and the stake pool program has been upgraded
+    #[inline]
+    pub const fn try_as_calc(&self) -> Result<Option<&SplCalc>, PoolProgUpgradedErr> {
+        match self.upgrade_check.check() {
+            Ok(()) => Ok(self.as_calc()),
+            Err(e) => Err(e),
+        }
+    }
+
     #[inline]
     pub const fn as_accs(&self) -> &A {
         &self.accs
     }
+
+    #[inline]
+    pub const fn lst_mint(&self) -> Option<&[u8; 32]> {
+        self.lst_mint.as_ref()
+    }
 }
+
+macro_rules! svc_ix_impl {
+    ($Ty:ty) => {
+        /// Instructions
+        impl $Ty {
+            /// Complete SolValCalc interface instruction for [`Self::lst_mint`].
+            ///
+            /// `amt` is LST amount for [`SvcIxTy::LstToSol`]
+            /// and lamport amount for [`SvcIxTy::SolToLst`].
+            ///
+            /// `None` if this calc has not been bound to a mint or updated yet.
+            #[inline]
+            pub fn svc_ix(
+                &self,
+                ty: SvcIxTy,
+                amt: u64,
+            ) -> Option<SvcIx<IxSufKeysOwned, IxSufAccFlags>> {
+                let lst_mint = *self.lst_mint()?;
+                let accs = self.as_accs();
+                Some(SvcIx::new(*accs.svc_program_id(), lst_mint, accs, ty, amt))
+            }
+        }
+    };
+}
+
+svc_ix_impl!(SanctumSplSvcStd);
+svc_ix_impl!(SanctumSplMultiSvcStd);
+svc_ix_impl!(SplSvcStd);
@@ -0,0 +1,331 @@
+//! Human-readable fixed-point decimal formatting of quotes
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use crate::traits::SolValCalc;
+
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Offset of the `decimals` field in token program (and token-2022) mint accounts
+pub const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Length of token program mint accounts.
+/// Token-2022 mint accounts with extensions are longer.
+pub const MINT_LEN: usize = 82;
+
+/// Returns `None` if `mint_acc_data` is shorter than [`MINT_LEN`]
+#[inline]
+pub fn mint_decimals(mint_acc_data: &[u8]) -> Option<u8> {
+    if mint_acc_data.len() < MINT_LEN {
+        return None;
+    }
+    mint_acc_data.get(MINT_DECIMALS_OFFSET).copied()
+}
+
+/// What to do with the digits beyond [`DecimalFmt::precision`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Truncate
+    #[default]
+    Down,
+
+    /// Round up if any truncated digit is nonzero
+    Up,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalFmt {
+    pub decimals: u8,
+
+    /// Number of digits to display after the decimal point,
+    /// clamped to `decimals`. `None` means display all `decimals` digits.
+    pub precision: Option<u8>,
+
+    pub rounding: Rounding,
+}
+
+/// Constructors
+impl DecimalFmt {
+    pub const SOL: Self = Self::new(SOL_DECIMALS);
+
+    #[inline]
+    pub const fn new(decimals: u8) -> Self {
+        Self {
+            decimals,
+            precision: None,
+            rounding: Rounding::Down,
+        }
+    }
+
+    /// Returns `None` if `mint_acc_data` is shorter than [`MINT_LEN`]
+    #[inline]
+    pub fn of_mint_acc_data(mint_acc_data: &[u8]) -> Option<Self> {
+        mint_decimals(mint_acc_data).map(Self::new)
+    }
+
+    #[inline]
+    pub const fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    #[inline]
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+}
+
+/// Display adapters
+impl DecimalFmt {
+    #[inline]
+    pub const fn amt(self, amt: u64) -> DecimalAmt {
+        DecimalAmt { amt, fmt: self }
+    }
+
+    #[inline]
+    pub const fn range(self, range: RangeInclusive<u64>) -> DecimalRange {
+        DecimalRange { range, fmt: self }
+    }
+}
+
+impl DecimalFmt {
+    #[inline]
+    fn fmt_amt(&self, amt: u64, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let decimals = u32::from(self.decimals);
+        let precision = self
+            .precision
+            .map_or(decimals, |p| u32::from(p).min(decimals));
+
+        // scale amt down to `precision` digits after the decimal point
+        let amt = u128::from(amt);
+        let scaled = match 10u128.checked_pow(decimals - precision) {
+            Some(div) => {
+                let q = amt / div;
+                match self.rounding {
+                    Rounding::Up if amt % div != 0 => q + 1,
+                    _ => q,
+                }
+            }
+            // div > u128::MAX > amt
+            None => match self.rounding {
+                Rounding::Up if amt != 0 => 1,
+                _ => 0,
+            },
+        };
+
+        match 10u128.checked_pow(precision) {
+            _ if precision == 0 => write!(f, "{scaled}"),
+            Some(one) => write!(
+                f,
+                "{}.{:0>width$}",
+                scaled / one,
+                scaled % one,
+                width = precision as usize
+            ),
+            // one > u128::MAX > scaled
+            None => write!(f, "0.{scaled:0>width$}", width = precision as usize),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalAmt {
+    pub amt: u64,
+    pub fmt: DecimalFmt,
+}
+
+impl Display for DecimalAmt {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt.fmt_amt(self.amt, f)
+    }
+}
+
+/// Displays a single value if both ends of the range are equal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecimalRange {
+    pub range: RangeInclusive<u64>,
+    pub fmt: DecimalFmt,
+}
+
+impl Display for DecimalRange {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        self.fmt.fmt_amt(start, f)?;
+        if start != end {
+            f.write_str(" - ")?;
+            self.fmt.fmt_amt(end, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A formatted quote: `{inp} = {out}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Quote {
+    pub inp: DecimalAmt,
+    pub out: DecimalRange,
+}
+
+impl Display for Quote {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.inp, self.out)
+    }
+}
+
+#[inline]
+pub fn lst_to_sol_quote<C: SolValCalc>(
+    calc: &C,
+    lst_amount: u64,
+    lst_fmt: DecimalFmt,
+    sol_fmt: DecimalFmt,
+) -> Result<Quote, C::Error> {
+    calc.lst_to_sol(lst_amount).map(|r| Quote {
+        inp: lst_fmt.amt(lst_amount),
+        out: sol_fmt.range(r),
+    })
+}
+
+#[inline]
+pub fn sol_to_lst_quote<C: SolValCalc>(
+    calc: &C,
+    lamports_amount: u64,
+    sol_fmt: DecimalFmt,
+    lst_fmt: DecimalFmt,
+) -> Result<Quote, C::Error> {
+    calc.sol_to_lst(lamports_amount).map(|r| Quote {
+        inp: sol_fmt.amt(lamports_amount),
+        out: lst_fmt.range(r),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mint_decimals_requires_mint_len() {
+        let mut data = vec![0; MINT_LEN];
+        data[MINT_DECIMALS_OFFSET] = 6;
+        assert_eq!(mint_decimals(&data), Some(6));
+        assert_eq!(mint_decimals(&data[..MINT_LEN - 1]), None);
+        assert_eq!(mint_decimals(&data[..MINT_DECIMALS_OFFSET + 1]), None);
+    }
+
+    #[test]
+    fn amt_zero() {
+        assert_eq!(DecimalFmt::SOL.amt(0).to_string(), "0.000000000");
+        assert_eq!(
+            DecimalFmt::SOL
+                .with_precision(0)
+                .with_rounding(Rounding::Up)
+                .amt(0)
+                .to_string(),
+            "0"
+        );
+    }
+
+    #[test]
+    fn amt_u64_max() {
+        assert_eq!(
+            DecimalFmt::SOL.amt(u64::MAX).to_string(),
+            "18446744073.709551615"
+        );
+        assert_eq!(
+            DecimalFmt::new(0).amt(u64::MAX).to_string(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            DecimalFmt::SOL.with_precision(2).amt(u64::MAX).to_string(),
+            "18446744073.70"
+        );
+        assert_eq!(
+            DecimalFmt::SOL
+                .with_precision(2)
+                .with_rounding(Rounding::Up)
+                .amt(u64::MAX)
+                .to_string(),
+            "18446744073.71"
+        );
+        // 10^decimals > u128::MAX
+        assert_eq!(
+            DecimalFmt::new(40).amt(u64::MAX).to_string(),
+            "0.0000000000000000000018446744073709551615"
+        );
+    }
+
+    #[test]
+    fn amt_rounding() {
+        let fmt = DecimalFmt::SOL.with_precision(2);
+        assert_eq!(fmt.amt(1_999_999_999).to_string(), "1.99");
+        assert_eq!(
+            fmt.with_rounding(Rounding::Up)
+                .amt(1_999_999_999)
+                .to_string(),
+            "2.00"
+        );
+        assert_eq!(
+            fmt.with_rounding(Rounding::Up)
+                .amt(1_500_000_000)
+                .to_string(),
+            "1.50"
+        );
+        assert_eq!(DecimalFmt::SOL.with_precision(0).amt(1).to_string(), "0");
+        assert_eq!(
+            DecimalFmt::SOL
+                .with_precision(0)
+                .with_rounding(Rounding::Up)
+                .amt(1)
+                .to_string(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn precision_clamped_to_decimals() {
+        assert_eq!(
+            DecimalFmt::new(2).with_precision(5).amt(12_345).to_string(),
+            "123.45"
+        );
+        assert_eq!(
+            DecimalFmt::new(0)
+                .with_precision(u8::MAX)
+                .amt(12_345)
+                .to_string(),
+            "12345"
+        );
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(DecimalFmt::SOL.range(1..=1).to_string(), "0.000000001");
+        assert_eq!(
+            DecimalFmt::SOL
+                .with_precision(1)
+                .range(1_000_000_000..=2_500_000_000)
+                .to_string(),
+            "1.0 - 2.5"
+        );
+        assert_eq!(
+            DecimalFmt::new(0).range(0..=u64::MAX).to_string(),
+            "0 - 18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn quote() {
+        let quote = Quote {
+            inp: DecimalFmt::new(6).amt(1_000_000),
+            out: DecimalFmt::SOL
+                .with_precision(3)
+                .range(1_100_000_000..=1_100_000_000),
+        };
+        assert_eq!(quote.to_string(), "1.000000 = 1.100");
+    }
+}
@@ -0,0 +1,358 @@
+//! Fetch-and-update loop for many calculators at once:
+//! [`AccountsToUpdateSvc::accounts_to_update_svc`] -> dedup -> fetch -> [`UpdateSvc::update_svc`]
+
+use std::{
+    collections::HashSet,
+    convert::Infallible,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+};
+
+use crate::update::{AccountsToUpdateSvc, OwnedAccount, OwnedUpdateMap, UpdateErr, UpdateSvc};
+
+pub trait AccountFetcher {
+    type Error: Error;
+
+    /// Returned vec must be of the same len and order as `pks`,
+    /// with `None` for accounts that do not exist
+    fn fetch_accounts(&self, pks: &[[u8; 32]]) -> Result<Vec<Option<OwnedAccount>>, Self::Error>;
+}
+
+/// Async version of [`AccountFetcher`]
+pub trait AsyncAccountFetcher {
+    type Error: Error;
+
+    /// Returned vec must be of the same len and order as `pks`,
+    /// with `None` for accounts that do not exist
+    fn fetch_accounts(
+        &self,
+        pks: &[[u8; 32]],
+    ) -> impl Future<Output = Result<Vec<Option<OwnedAccount>>, Self::Error>>;
+}
+
+/// In-memory fetcher for offline use
+impl AccountFetcher for OwnedUpdateMap {
+    type Error = Infallible;
+
+    #[inline]
+    fn fetch_accounts(&self, pks: &[[u8; 32]]) -> Result<Vec<Option<OwnedAccount>>, Self::Error> {
+        Ok(pks.iter().map(|pk| self.0.get(pk).cloned()).collect())
+    }
+}
+
+impl AsyncAccountFetcher for OwnedUpdateMap {
+    type Error = Infallible;
+
+    #[inline]
+    fn fetch_accounts(
+        &self,
+        pks: &[[u8; 32]],
+    ) -> impl Future<Output = Result<Vec<Option<OwnedAccount>>, Self::Error>> {
+        std::future::ready(AccountFetcher::fetch_accounts(self, pks))
+    }
+}
+
+/// Per-calculator update results, in the same order as the calculators passed in
+pub type UpdateResults<E> = Vec<Result<(), UpdateErr<E>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpdateDriver {
+    /// Max number of accounts to fetch per [`AccountFetcher::fetch_accounts`] call.
+    pub batch_size: usize,
+}
+
+/// Constructors
+impl UpdateDriver {
+    /// Max number of accounts per `getMultipleAccounts` RPC call
+    pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+    pub const DEFAULT: Self = Self::new(Self::DEFAULT_BATCH_SIZE);
+
+    /// `batch_size` of 0 is treated as 1
+    #[inline]
+    pub const fn new(batch_size: usize) -> Self {
+        Self { batch_size }
+    }
+}
+
+impl Default for UpdateDriver {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Deduped union of all accounts required to update `svcs`,
+/// in order of first occurence
+pub fn accounts_to_update_all<'a, S: AccountsToUpdateSvc + 'a>(
+    svcs: impl IntoIterator<Item = &'a S>,
+) -> Vec<[u8; 32]> {
+    let mut seen = HashSet::new();
+    svcs.into_iter()
+        .flat_map(AccountsToUpdateSvc::accounts_to_update_svc)
+        .filter(|pk| seen.insert(*pk))
+        .collect()
+}
+
+/// Sync
+impl UpdateDriver {
+    /// Fetches all required accounts then updates all `svcs`,
+    /// continuing even if some of them fail.
+    ///
+    /// Returns `Err` only if fetching failed, in which case no calculators are updated.
+    pub fn update_all<'a, S, F>(
+        &self,
+        fetcher: &F,
+        svcs: impl IntoIterator<Item = &'a mut S>,
+    ) -> Result<UpdateResults<S::InnerErr>, FetchErr<F::Error>>
+    where
+        S: AccountsToUpdateSvc + UpdateSvc + 'a,
+        F: AccountFetcher,
+    {
+        let mut svcs: Vec<&mut S> = svcs.into_iter().collect();
+        let pks = accounts_to_update_all(svcs.iter().map(|s| &**s));
+        let mut fetched = OwnedUpdateMap::new();
+        for batch in pks.chunks(self.batch_size.max(1)) {
+            let accs = fetcher.fetch_accounts(batch).map_err(FetchErr::Fetcher)?;
+            fetched.extend(fetched_pairs(batch, accs)?);
+        }
+        Ok(update_all_with(&fetched, &mut svcs))
+    }
+}
+
+/// Async
+impl UpdateDriver {
+    /// Async version of [`Self::update_all`].
+    ///
+    /// Batches are fetched sequentially.
+    pub async fn update_all_async<'a, S, F>(
+        &self,
+        fetcher: &F,
+        svcs: impl IntoIterator<Item = &'a mut S>,
+    ) -> Result<UpdateResults<S::InnerErr>, FetchErr<F::Error>>
+    where
+        S: AccountsToUpdateSvc + UpdateSvc + 'a,
+        F: AsyncAccountFetcher,
+    {
+        let mut svcs: Vec<&mut S> = svcs.into_iter().collect();
+        let pks = accounts_to_update_all(svcs.iter().map(|s| &**s));
+        let mut fetched = OwnedUpdateMap::new();
+        for batch in pks.chunks(self.batch_size.max(1)) {
+            let accs = fetcher
+                .fetch_accounts(batch)
+                .await
+                .map_err(FetchErr::Fetcher)?;
+            fetched.extend(fetched_pairs(batch, accs)?);
+        }
+        Ok(update_all_with(&fetched, &mut svcs))
+    }
+}
+
+/// Errors if the fetcher did not return exactly one entry per requested pubkey
+fn fetched_pairs<E>(
+    pks: &[[u8; 32]],
+    accs: Vec<Option<OwnedAccount>>,
+) -> Result<impl Iterator<Item = ([u8; 32], OwnedAccount)> + '_, FetchErr<E>> {
+    if accs.len() != pks.len() {
+        return Err(FetchErr::LenMismatch {
+            expected: pks.len(),
+            actual: accs.len(),
+        });
+    }
+    Ok(pks
+        .iter()
+        .zip(accs)
+        .filter_map(|(pk, acc)| acc.map(|acc| (*pk, acc))))
+}
+
+fn update_all_with<S: UpdateSvc>(
+    fetched: &OwnedUpdateMap,
+    svcs: &mut [&mut S],
+) -> UpdateResults<S::InnerErr> {
+    svcs.iter_mut().map(|s| s.update_svc(fetched)).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FetchErr<E> {
+    Fetcher(E),
+
+    /// Fetcher returned a different number of accounts than requested
+    LenMismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl<E: Display> Display for FetchErr<E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetcher(e) => write!(f, "Fetcher::{e}"),
+            Self::LenMismatch { expected, actual } => {
+                write!(f, "expected {expected} fetched accounts, got {actual}")
+            }
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for FetchErr<E> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Fetcher(e) => Some(e),
+            Self::LenMismatch { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::update::{Account, UpdateMap};
+
+    use super::*;
+
+    /// Requires all its accounts, records their data on update
+    #[derive(Debug, Default)]
+    struct MockSvc {
+        pks: Vec<[u8; 32]>,
+        updated: Option<Vec<Vec<u8>>>,
+    }
+
+    impl MockSvc {
+        fn new(pks: impl IntoIterator<Item = u8>) -> Self {
+            Self {
+                pks: pks.into_iter().map(|b| [b; 32]).collect(),
+                updated: None,
+            }
+        }
+    }
+
+    impl AccountsToUpdateSvc for MockSvc {
+        type PkIter = std::vec::IntoIter<[u8; 32]>;
+
+        fn accounts_to_update_svc(&self) -> Self::PkIter {
+            self.pks.clone().into_iter()
+        }
+    }
+
+    impl UpdateSvc for MockSvc {
+        type InnerErr = Infallible;
+
+        fn update_svc(&mut self, update_map: impl UpdateMap) -> Result<(), UpdateErr<Infallible>> {
+            let mut data = Vec::new();
+            for pk in self.pks.iter() {
+                data.push(update_map.get_account_checked(pk)?.data().to_vec());
+            }
+            self.updated = Some(data);
+            Ok(())
+        }
+    }
+
+    /// Records requested batches, optionally dropping the last fetched account
+    struct MockFetcher {
+        accs: OwnedUpdateMap,
+        batches: RefCell<Vec<Vec<[u8; 32]>>>,
+        drop_last: bool,
+    }
+
+    impl MockFetcher {
+        fn new(pks: impl IntoIterator<Item = u8>) -> Self {
+            Self {
+                accs: pks
+                    .into_iter()
+                    .map(|b| {
+                        (
+                            [b; 32],
+                            OwnedAccount {
+                                data: vec![b],
+                                ..Default::default()
+                            },
+                        )
+                    })
+                    .collect(),
+                batches: RefCell::default(),
+                drop_last: false,
+            }
+        }
+    }
+
+    impl AccountFetcher for MockFetcher {
+        type Error = Infallible;
+
+        fn fetch_accounts(
+            &self,
+            pks: &[[u8; 32]],
+        ) -> Result<Vec<Option<OwnedAccount>>, Self::Error> {
+            self.batches.borrow_mut().push(pks.to_vec());
+            let mut res = AccountFetcher::fetch_accounts(&self.accs, pks)?;
+            if self.drop_last {
+                res.pop();
+            }
+            Ok(res)
+        }
+    }
+
+    #[test]
+    fn accounts_to_update_all_dedups_in_order() {
+        let svcs = [
+            MockSvc::new([1, 2]),
+            MockSvc::new([2, 3]),
+            MockSvc::new([3, 1]),
+        ];
+        assert_eq!(accounts_to_update_all(&svcs), [[1; 32], [2; 32], [3; 32]]);
+    }
+
+    #[test]
+    fn update_all_batches() {
+        let mut svcs = [
+            MockSvc::new([1, 2]),
+            MockSvc::new([2, 3]),
+            MockSvc::new([4, 5]),
+        ];
+        // account 5 does not exist
+        let fetcher = MockFetcher::new([1, 2, 3, 4]);
+
+        let res = UpdateDriver::new(2)
+            .update_all(&fetcher, &mut svcs)
+            .unwrap();
+
+        assert_eq!(
+            *fetcher.batches.borrow(),
+            [
+                vec![[1; 32], [2; 32]],
+                vec![[3; 32], [4; 32]],
+                vec![[5; 32]]
+            ]
+        );
+        assert_eq!(
+            res,
+            [Ok(()), Ok(()), Err(UpdateErr::AccMissing { pk: [5; 32] })]
+        );
+        assert_eq!(svcs[0].updated, Some(vec![vec![1], vec![2]]));
+        assert_eq!(svcs[1].updated, Some(vec![vec![2], vec![3]]));
+        assert_eq!(svcs[2].updated, None);
+    }
+
+    #[test]
+    fn update_all_len_mismatch() {
+        let mut svcs = [MockSvc::new([1, 2, 3])];
+        let fetcher = MockFetcher {
+            drop_last: true,
+            ..MockFetcher::new([1, 2, 3])
+        };
+
+        let res = UpdateDriver::new(2).update_all(&fetcher, &mut svcs);
+
+        assert_eq!(
+            res,
+            Err(FetchErr::LenMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(svcs[0].updated, None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Loader for accounts in the `solana account --output json` format,
+//! e.g. `test-fixtures/*.json`:
+//!
+//! ```json
+//! {
+//!   "pubkey": "<base58>",
+//!   "account": {
+//!     "lamports": 1169280,
+//!     "data": ["<base64>", "base64"],
+//!     "owner": "<base58>",
+//!     "executable": false,
+//!     "rentEpoch": 18446744073709551615,
+//!     "space": 40
+//!   }
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+
+use crate::update::{Account, AccountOwner, OwnedAccount, OwnedUpdateMap, UpdateMap};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonAccount {
+    pub pubkey: [u8; 32],
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: [u8; 32],
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+/// Constructors
+impl JsonAccount {
+    pub fn from_json_slice(json: &[u8]) -> Result<Self, JsonAccountErr> {
+        let json: Value = serde_json::from_slice(json).map_err(JsonAccountErr::Json)?;
+        let acc = &json["account"];
+        let [data, encoding] = [&acc["data"][0], &acc["data"][1]]
+            .map(|v| v.as_str().ok_or(JsonAccountErr::Field("data")));
+        let (data, encoding) = (data?, encoding?);
+        if encoding != "base64" {
+            return Err(JsonAccountErr::UnsupportedEncoding(encoding.to_owned()));
+        }
+        Ok(Self {
+            pubkey: pubkey_field(&json["pubkey"], "pubkey")?,
+            lamports: acc["lamports"]
+                .as_u64()
+                .ok_or(JsonAccountErr::Field("lamports"))?,
+            data: STANDARD
+                .decode(data)
+                .map_err(|_| JsonAccountErr::Field("data"))?,
+            owner: pubkey_field(&acc["owner"], "owner")?,
+            executable: acc["executable"]
+                .as_bool()
+                .ok_or(JsonAccountErr::Field("executable"))?,
+            rent_epoch: acc["rentEpoch"]
+                .as_u64()
+                .ok_or(JsonAccountErr::Field("rentEpoch"))?,
+        })
+    }
+
+    pub fn read_file(path: impl AsRef<Path>) -> Result<Self, JsonAccountErr> {
+        Self::from_json_slice(&fs::read(path).map_err(JsonAccountErr::Io)?)
+    }
+}
+
+/// Conversions
+impl JsonAccount {
+    /// See [`OwnedAccount`]'s `From<JsonAccount>`
+    #[inline]
+    pub fn into_keyed_owned(self) -> ([u8; 32], OwnedAccount) {
+        (self.pubkey, self.into())
+    }
+}
+
+/// `slot` is set to 0 since the JSON format does not record it
+impl From<JsonAccount> for OwnedAccount {
+    #[inline]
+    fn from(
+        JsonAccount {
+            lamports,
+            data,
+            owner,
+            ..
+        }: JsonAccount,
+    ) -> Self {
+        Self {
+            data,
+            owner,
+            lamports,
+            slot: 0,
+        }
+    }
+}
+
+impl Account for JsonAccount {
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AccountOwner for JsonAccount {
+    #[inline]
+    fn owner(&self) -> [u8; 32] {
+        self.owner
+    }
+}
+
+fn pubkey_field(v: &Value, field: &'static str) -> Result<[u8; 32], JsonAccountErr> {
+    let mut pk = [0u8; 32];
+    match v.as_str().map(|s| bs58::decode(s).onto(&mut pk)) {
+        Some(Ok(32)) => Ok(pk),
+        _ => Err(JsonAccountErr::Field(field)),
+    }
+}
+
+/// In-memory account store keyed by pubkey
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JsonAccounts(pub HashMap<[u8; 32], JsonAccount>);
+
+/// Constructors
+impl JsonAccounts {
+    pub fn read_file(path: impl AsRef<Path>) -> Result<Self, JsonAccountErr> {
+        JsonAccount::read_file(path).map(|a| [a].into_iter().collect())
+    }
+
+    /// Reads all `*.json` files in `dir`, non-recursive
+    pub fn read_dir(dir: impl AsRef<Path>) -> Result<Self, JsonAccountErr> {
+        let mut res = Self::default();
+        res.extend_from_dir(dir)?;
+        Ok(res)
+    }
+}
+
+/// Mutators
+impl JsonAccounts {
+    /// Replaces any existing account with the same pubkey
+    #[inline]
+    pub fn insert(&mut self, acc: JsonAccount) -> Option<JsonAccount> {
+        self.0.insert(acc.pubkey, acc)
+    }
+
+    pub fn extend_from_file(&mut self, path: impl AsRef<Path>) -> Result<(), JsonAccountErr> {
+        JsonAccount::read_file(path).map(|a| {
+            self.insert(a);
+        })
+    }
+
+    /// Reads all `*.json` files in `dir`, non-recursive
+    pub fn extend_from_dir(&mut self, dir: impl AsRef<Path>) -> Result<(), JsonAccountErr> {
+        fs::read_dir(dir)
+            .map_err(JsonAccountErr::Io)?
+            .try_for_each(|entry| {
+                let path = entry.map_err(JsonAccountErr::Io)?.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+                    self.extend_from_file(path)
+                } else {
+                    Ok(())
+                }
+            })
+    }
+}
+
+impl FromIterator<JsonAccount> for JsonAccounts {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = JsonAccount>>(iter: T) -> Self {
+        Self(iter.into_iter().map(|a| (a.pubkey, a)).collect())
+    }
+}
+
+impl Extend<JsonAccount> for JsonAccounts {
+    #[inline]
+    fn extend<T: IntoIterator<Item = JsonAccount>>(&mut self, iter: T) {
+        self.0.extend(iter.into_iter().map(|a| (a.pubkey, a)))
+    }
+}
+
+impl From<JsonAccounts> for OwnedUpdateMap {
+    #[inline]
+    fn from(JsonAccounts(accs): JsonAccounts) -> Self {
+        accs.into_iter().map(|(pk, a)| (pk, a.into())).collect()
+    }
+}
+
+impl UpdateMap for JsonAccounts {
+    type Account<'a> = &'a JsonAccount;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        self.0.get(pk)
+    }
+}
+
+#[derive(Debug)]
+pub enum JsonAccountErr {
+    Io(io::Error),
+    Json(serde_json::Error),
+
+    /// Field missing or malformed
+    Field(&'static str),
+
+    /// Account data encoding other than `base64`
+    UnsupportedEncoding(String),
+}
+
+impl Display for JsonAccountErr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io: {e}"),
+            Self::Json(e) => write!(f, "json: {e}"),
+            Self::Field(field) => write!(f, "invalid field {field}"),
+            Self::UnsupportedEncoding(enc) => write!(f, "unsupported data encoding {enc}"),
+        }
+    }
+}
+
+impl Error for JsonAccountErr {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Field(_) | Self::UnsupportedEncoding(_) => None,
+        }
+    }
+}
@@ -1,4 +1,10 @@
 // Re-exports
 pub use sanctum_svc_core::*;
 
+pub mod display;
+pub mod driver;
+
+#[cfg(feature = "json")]
+pub mod json;
+
 pub mod update;
@@ -0,0 +1,89 @@
+//! Minimal SolValCalc program built on [`SvcProgram`],
+//! used to test [`process_svc_ix`] in an in-process SVM.
+//!
+//! Quotes wSOL 1:1 and expects a single readonly [`CONFIG`] account
+//! after the LST mint so that the accounts suffix checks are exercised.
+
+use core::convert::Infallible;
+
+use jiminy_cpi::{
+    account::{Abr, AccountHandle},
+    program_error::{ProgramError, INVALID_ARGUMENT},
+};
+use sanctum_svc_jiminy::{
+    program::{process_svc_ix, SvcProgram},
+    sanctum_svc_ag_core::sanctum_svc_wsol_core::{calc::WsolCalc, LST_MINT},
+    traits::SolValCalcAccs,
+};
+
+const MAX_ACCS: usize = 4;
+
+/// The only accounts suffix account
+pub const CONFIG: [u8; 32] = [1; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TestCalcAccs;
+
+impl SolValCalcAccs for TestCalcAccs {
+    type KeysOwned = [[u8; 32]; 1];
+
+    type AccFlags = [bool; 1];
+
+    #[inline]
+    fn suf_keys_owned(&self) -> Self::KeysOwned {
+        [CONFIG]
+    }
+
+    #[inline]
+    fn suf_is_writer(&self) -> Self::AccFlags {
+        [false]
+    }
+
+    #[inline]
+    fn suf_is_signer(&self) -> Self::AccFlags {
+        [false]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TestProgram;
+
+impl SvcProgram for TestProgram {
+    type Calc = WsolCalc;
+    type Accs = TestCalcAccs;
+
+    #[inline]
+    fn calc_accs(&self, lst_mint: &[u8; 32]) -> Result<Self::Accs, ProgramError> {
+        if *lst_mint == LST_MINT {
+            Ok(TestCalcAccs)
+        } else {
+            Err(INVALID_ARGUMENT)
+        }
+    }
+
+    #[inline]
+    fn calc(
+        &self,
+        _abr: &Abr,
+        _lst_mint: AccountHandle<'_>,
+        _suf: &[AccountHandle<'_>],
+    ) -> Result<Self::Calc, ProgramError> {
+        Ok(WsolCalc)
+    }
+
+    #[inline]
+    fn calc_err(&self, e: Infallible) -> ProgramError {
+        match e {}
+    }
+}
+
+jiminy_entrypoint::program_entrypoint!(process_ix, MAX_ACCS);
+
+fn process_ix(
+    abr: &mut Abr,
+    accounts: &[AccountHandle],
+    data: &[u8],
+    _prog_id: &[u8; 32],
+) -> Result<(), ProgramError> {
+    process_svc_ix(&TestProgram, abr, accounts, data)
+}
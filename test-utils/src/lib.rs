@@ -0,0 +1,163 @@
+//! Property tests that every [`SolValCalc`] implementation must pass.
+//!
+//! Plug a [`Strategy`] generating randomised calculator state into [`check_conformance`],
+//! or use the individual `prop_*` fns in your own `proptest!` blocks.
+//!
+//! Properties are only checked for calls that return `Ok`:
+//! failing calls, e.g. because the pool has not been updated for the epoch,
+//! are permitted, panicking calls are not.
+//!
+//! Also contains loaders and builders for the account fixtures in `test-fixtures/`.
+
+use core::{fmt::Debug, ops::RangeInclusive};
+use std::{fs, path::PathBuf};
+
+use proptest::{
+    prelude::*,
+    test_runner::{TestCaseResult, TestRunner},
+};
+use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_std::json::JsonAccount;
+use solana_pubkey::Pubkey;
+
+// Re-exports
+pub use proptest;
+
+// Fixtures
+
+pub const SYSVAR_CLOCK: Pubkey =
+    solana_pubkey::pubkey!("SysvarC1ock11111111111111111111111111111111");
+
+pub fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("test-fixtures")
+}
+
+/// `test-fixtures/{name}.json`
+pub fn fixture_acc(name: &str) -> JsonAccount {
+    JsonAccount::read_file(fixtures_dir().join(format!("{name}.json"))).unwrap()
+}
+
+/// `test-fixtures/programs/{file}`
+pub fn read_program(file: &str) -> Vec<u8> {
+    fs::read(fixtures_dir().join("programs").join(file)).unwrap()
+}
+
+/// Serialized `Clock` sysvar account data
+pub fn clock_data(epoch: u64) -> Vec<u8> {
+    let mut data = vec![0; 40];
+    data[16..24].copy_from_slice(&epoch.to_le_bytes());
+    data
+}
+
+/// bincode-serialized `UpgradeableLoaderState::ProgramData` header
+/// of a program last deployed/upgraded at `slot`
+pub fn progdata_header(slot: u64) -> Vec<u8> {
+    3u32.to_le_bytes()
+        .into_iter()
+        .chain(slot.to_le_bytes())
+        .chain([1])
+        .chain([0; 32])
+        .collect()
+}
+
+// Conformance
+
+/// Amounts to quote, biased towards small values
+/// since uniformly random `u64`s are almost always unrealistically large
+pub fn any_amt() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        Just(0),
+        Just(u64::MAX),
+        0..=1_000_000_000_000_000,
+        any::<u64>(),
+    ]
+}
+
+/// Runs all conformance properties against `calc_strat` with the default proptest config,
+/// panicking on failure
+pub fn check_conformance<C: SolValCalc + Debug>(calc_strat: impl Strategy<Value = C>) {
+    TestRunner::default()
+        .run(&(calc_strat, any_amt(), any_amt()), |(calc, a, b)| {
+            prop_conforms(&calc, a, b)
+        })
+        .unwrap();
+}
+
+/// All properties below
+pub fn prop_conforms(calc: &impl SolValCalc, a: u64, b: u64) -> TestCaseResult {
+    prop_edge_cases_no_panic(calc);
+    [a, b].into_iter().try_for_each(|amt| {
+        prop_ranges_nonempty(calc, amt)?;
+        prop_lst_to_sol_round_trip(calc, amt)
+    })?;
+    prop_lst_to_sol_monotonic(calc, a, b)?;
+    prop_sol_to_lst_monotonic(calc, a, b)
+}
+
+/// Quoting the edge-case amounts `0` and `u64::MAX`
+/// must not panic, but may return `Err`
+pub fn prop_edge_cases_no_panic(calc: &impl SolValCalc) {
+    [0, u64::MAX].into_iter().for_each(|amt| {
+        let _ = calc.lst_to_sol(amt);
+        let _ = calc.sol_to_lst(amt);
+    });
+}
+
+/// Returned ranges must have `start <= end`
+pub fn prop_ranges_nonempty(calc: &impl SolValCalc, amt: u64) -> TestCaseResult {
+    if let Ok(r) = calc.lst_to_sol(amt) {
+        prop_assert!(!r.is_empty(), "lst_to_sol({amt}) = {r:?}");
+    }
+    if let Ok(r) = calc.sol_to_lst(amt) {
+        prop_assert!(!r.is_empty(), "sol_to_lst({amt}) = {r:?}");
+    }
+    Ok(())
+}
+
+/// More LST must never be worth less SOL
+pub fn prop_lst_to_sol_monotonic(calc: &impl SolValCalc, a: u64, b: u64) -> TestCaseResult {
+    prop_monotonic(|amt| calc.lst_to_sol(amt).ok(), a, b)
+}
+
+/// More SOL must never be worth less LST
+pub fn prop_sol_to_lst_monotonic(calc: &impl SolValCalc, a: u64, b: u64) -> TestCaseResult {
+    prop_monotonic(|amt| calc.sol_to_lst(amt).ok(), a, b)
+}
+
+/// `lst_amount` must lie within the range of LST amounts that
+/// `sol_to_lst` returns for the SOL values `lst_to_sol(lst_amount)` returns
+pub fn prop_lst_to_sol_round_trip(calc: &impl SolValCalc, lst_amount: u64) -> TestCaseResult {
+    let sol = match calc.lst_to_sol(lst_amount) {
+        Ok(r) => r,
+        Err(_) => return Ok(()),
+    };
+    let (min, max) = match (calc.sol_to_lst(*sol.start()), calc.sol_to_lst(*sol.end())) {
+        (Ok(min), Ok(max)) => (min, max),
+        _ => return Ok(()),
+    };
+    prop_assert!(
+        (*min.start()..=*max.end()).contains(&lst_amount),
+        "lst_to_sol({lst_amount}) = {sol:?}, sol_to_lst({}) = {min:?}, sol_to_lst({}) = {max:?}",
+        sol.start(),
+        sol.end(),
+    );
+    Ok(())
+}
+
+fn prop_monotonic(
+    f: impl Fn(u64) -> Option<RangeInclusive<u64>>,
+    a: u64,
+    b: u64,
+) -> TestCaseResult {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    if let (Some(rlo), Some(rhi)) = (f(lo), f(hi)) {
+        prop_assert!(
+            rlo.start() <= rhi.start() && rlo.end() <= rhi.end(),
+            "f({lo}) = {rlo:?}, f({hi}) = {rhi:?}"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use sanctum_svc_ag_core::{
+    calc::SvcCalcAg,
+    sanctum_svc_core::traits::{LstToSolRatios, SolValCalc, SolValCalcRatios},
+    sanctum_svc_lido_core::{calc::LidoCalc, solido_legacy_core::ExchangeRate},
+    sanctum_svc_marinade_core::calc::MarinadeCalc,
+    sanctum_svc_spl_core::{calc::SplCalc, sanctum_spl_stake_pool_core::Fee},
+    sanctum_svc_wsol_core::calc::WsolCalc,
+    SvcAg,
+};
+use sanctum_svc_test_utils::{any_amt, check_conformance, proptest::prelude::*};
+
+/// Small range so that both updated and not-yet-updated states are generated
+fn any_epoch() -> impl Strategy<Value = u64> {
+    0..=3u64
+}
+
+fn any_spl_calc() -> impl Strategy<Value = SplCalc> {
+    (
+        any_epoch(),
+        any_epoch(),
+        any::<u64>(),
+        1..=u64::MAX,
+        any_spl_fee(),
+    )
+        .prop_map(
+            |(
+                last_update_epoch,
+                current_epoch,
+                total_lamports,
+                pool_token_supply,
+                stake_withdrawal_fee,
+            )| SplCalc {
+                last_update_epoch,
+                total_lamports,
+                pool_token_supply,
+                stake_withdrawal_fee,
+                current_epoch,
+            },
+        )
+}
+
+/// Includes the 0-denominator fees that the SPL stake pool program permits.
+/// Excludes 100% fees.
+fn any_spl_fee() -> impl Strategy<Value = Fee> {
+    any::<u64>().prop_flat_map(|denominator| {
+        (0..denominator.max(1)).prop_map(move |numerator| Fee {
+            denominator,
+            numerator,
+        })
+    })
+}
+
+fn any_marinade_calc() -> impl Strategy<Value = MarinadeCalc> {
+    // MarinadeCalc sums these balances with unchecked arithmetic
+    // since they can never exceed SOL supply onchain,
+    // so keep their sum < u64::MAX
+    const MAX_BAL: u64 = u64::MAX / 8;
+    (
+        [0..=MAX_BAL, 0..=MAX_BAL, 0..=MAX_BAL, 0..=MAX_BAL, 0..=MAX_BAL],
+        1..=u64::MAX,
+        // exclude 100% fee = 1_000_000 bp cents
+        0..1_000_000u32,
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(
+                [available_reserve_balance, circulating_ticket_balance, delayed_unstake_cooling_down, emergency_cooling_down, total_active_balance],
+                msol_supply,
+                withdraw_stake_account_fee_cents,
+                withdraw_stake_account_enabled,
+                paused,
+            )| MarinadeCalc {
+                available_reserve_balance,
+                circulating_ticket_balance,
+                delayed_unstake_cooling_down,
+                emergency_cooling_down,
+                msol_supply,
+                total_active_balance,
+                withdraw_stake_account_fee_cents,
+                withdraw_stake_account_enabled,
+                paused,
+            },
+        )
+}
+
+fn any_lido_calc() -> impl Strategy<Value = LidoCalc> {
+    (any_epoch(), any_epoch(), 1..=u64::MAX, any::<u64>()).prop_map(
+        |(computed_in_epoch, current_epoch, st_sol_supply, sol_balance)| LidoCalc {
+            exchange_rate: ExchangeRate {
+                computed_in_epoch,
+                st_sol_supply,
+                sol_balance,
+            },
+            current_epoch,
+        },
+    )
+}
+
+fn any_svc_calc_ag() -> impl Strategy<Value = SvcCalcAg> {
+    prop_oneof![
+        any_lido_calc().prop_map(SvcAg::Lido),
+        any_marinade_calc().prop_map(SvcAg::Marinade),
+        any_spl_calc().prop_map(SvcAg::SanctumSpl),
+        any_spl_calc().prop_map(SvcAg::SanctumSplMulti),
+        any_spl_calc().prop_map(SvcAg::Spl),
+        Just(SvcAg::Wsol(WsolCalc)),
+    ]
+}
+
+#[test]
+fn spl_conformance() {
+    check_conformance(any_spl_calc());
+}
+
+#[test]
+fn marinade_conformance() {
+    check_conformance(any_marinade_calc());
+}
+
+#[test]
+fn lido_conformance() {
+    check_conformance(any_lido_calc());
+}
+
+#[test]
+fn wsol_conformance() {
+    check_conformance(Just(WsolCalc));
+}
+
+#[test]
+fn svc_calc_ag_conformance() {
+    check_conformance(any_svc_calc_ag());
+}
+
+// Ratios
+//
+// Applying `lst_to_sol_ratios` with each calculator's own rounding
+// must give exactly `lst_to_sol`
+
+proptest! {
+    #[test]
+    fn spl_ratios_match_lst_to_sol(calc in any_spl_calc(), amt in any_amt()) {
+        let (Ok(ratios), Ok(out)) = (calc.lst_to_sol_ratios(), calc.lst_to_sol(amt)) else {
+            return Ok(());
+        };
+        prop_assert_ne!(ratios.post_fee.d, 0);
+        // fee charged first, rounding the fee up, then pre_fee, rounding down
+        let expected = match one_minus_fee(&ratios) {
+            Some((n, d)) => {
+                let rem = mul_div(u128::from(amt), n, d, false);
+                mul_div(rem, ratios.pre_fee.n.into(), ratios.pre_fee.d.into(), false)
+            }
+            None => 0,
+        };
+        prop_assert_eq!(range_u128(out), expected..=expected);
+    }
+
+    #[test]
+    fn marinade_ratios_match_lst_to_sol(calc in any_marinade_calc(), amt in any_amt()) {
+        let (Ok(ratios), Ok(out)) = (calc.lst_to_sol_ratios(), calc.lst_to_sol(amt)) else {
+            return Ok(());
+        };
+        prop_assert_ne!(ratios.post_fee.d, 0);
+        // pre_fee first, rounding down, then fee charged, rounding the fee down
+        let expected = match one_minus_fee(&ratios) {
+            Some((n, d)) => {
+                let sol = mul_div(
+                    u128::from(amt),
+                    ratios.pre_fee.n.into(),
+                    ratios.pre_fee.d.into(),
+                    false,
+                );
+                mul_div(sol, n, d, true)
+            }
+            None => 0,
+        };
+        prop_assert_eq!(range_u128(out), expected..=expected);
+    }
+
+    #[test]
+    fn lido_ratios_match_lst_to_sol(calc in any_lido_calc(), amt in any_amt()) {
+        let (Ok(ratios), Ok(out)) = (calc.lst_to_sol_ratios(), calc.lst_to_sol(amt)) else {
+            return Ok(());
+        };
+        prop_assert_eq!(ratios, LstToSolRatios::no_fee(ratios.pre_fee));
+        let expected = mul_div(
+            u128::from(amt),
+            ratios.pre_fee.n.into(),
+            ratios.pre_fee.d.into(),
+            false,
+        );
+        prop_assert_eq!(range_u128(out), expected..=expected);
+    }
+
+    #[test]
+    fn wsol_ratios_match_lst_to_sol(amt in any_amt()) {
+        let ratios = WsolCalc.lst_to_sol_ratios().unwrap();
+        prop_assert_eq!(ratios.post_fee.n, ratios.post_fee.d);
+        prop_assert_eq!(WsolCalc.lst_to_sol(amt).unwrap(), amt..=amt);
+    }
+}
+
+/// `1 - fee` recovered from the unreduced `post_fee = pre_fee * (1 - fee)`,
+/// `None` if `pre_fee` is 0
+fn one_minus_fee(LstToSolRatios { pre_fee, post_fee }: &LstToSolRatios) -> Option<(u128, u128)> {
+    if pre_fee.n == 0 {
+        return None;
+    }
+    Some((
+        post_fee.n / u128::from(pre_fee.n),
+        post_fee.d / u128::from(pre_fee.d),
+    ))
+}
+
+/// `amt * n / d`. `amt` and `n` must each fit in a u64.
+fn mul_div(amt: u128, n: u128, d: u128, ceil: bool) -> u128 {
+    let p = amt * n;
+    if ceil {
+        p.div_ceil(d)
+    } else {
+        p / d
+    }
+}
+
+fn range_u128(r: core::ops::RangeInclusive<u64>) -> core::ops::RangeInclusive<u128> {
+    u128::from(*r.start())..=u128::from(*r.end())
+}
@@ -0,0 +1,354 @@
+//! Differential tests of [`SvcCalcAg`] against the onchain calculator programs
+//! in `test-fixtures/programs/`, executed in an in-process SVM.
+//!
+//! There are no pool or calculator state fixtures for
+//! the `spl` and `sanctum-spl` calculator programs, so they are not covered here.
+//!
+//! The CPI-free calculator of `sanctum-svc-jiminy`'s `no_cpi` module
+//! is also checked against the same fixtures, and against [`SvcAgStd`]
+//! for `spl` and `sanctum-spl` using the jupsol pool re-owned by their stake pool programs.
+
+use mollusk_svm::{program::loader_keys::LOADER_V3, Mollusk};
+use sanctum_svc_ag_core::sanctum_svc_spl_core::{
+    keys::{sanctum_spl, sanctum_spl_multi, spl},
+    sanctum_spl_stake_pool_core::StakePool,
+};
+use sanctum_svc_ag_std::{
+    calc::SvcCalcAg,
+    sanctum_svc_core::{
+        instructions::{return_data::decode_return_data, svc_ix::SvcIxTy},
+        traits::{SolValCalc, SolValCalcAccs},
+    },
+    update::{OwnedAccount, OwnedUpdateMap, UpdateSvc},
+    SvcAg, SvcAgStd, SvcCalcStdInitData,
+};
+use sanctum_svc_jiminy::no_cpi::{svc_calc_ag_of_accs, NoCpiErr, PoolStateAcc};
+use sanctum_svc_std::json::JsonAccount;
+use sanctum_svc_test_utils::{
+    clock_data, fixture_acc, progdata_header, read_program, SYSVAR_CLOCK,
+};
+use solana_account::Account;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+const AMTS: [u64; 6] = [
+    0,
+    1,
+    1_000_000_000,
+    123_456_789_012,
+    1_000_000_000_000_000,
+    u64::MAX,
+];
+
+struct Case {
+    init: SvcCalcStdInitData,
+    /// Onchain calculator program binary
+    prog: &'static str,
+    /// Stake pool program binary, if any
+    pool_prog: Option<&'static str>,
+    /// Fixture account files
+    accs: &'static [&'static str],
+    mint: &'static str,
+}
+
+#[test]
+fn lido_matches_onchain() {
+    Case {
+        init: SvcAg::Lido(()),
+        prog: "lido-calc",
+        pool_prog: Some("lido"),
+        accs: &["stsol-pool", "lido-calc-state"],
+        mint: "stsol-mint",
+    }
+    .assert_matches_onchain();
+}
+
+#[test]
+fn marinade_matches_onchain() {
+    Case {
+        init: SvcAg::Marinade(()),
+        prog: "marinade-calc",
+        pool_prog: Some("marinade"),
+        accs: &["msol-pool", "marinade-calc-state"],
+        mint: "msol-mint",
+    }
+    .assert_matches_onchain();
+}
+
+#[test]
+fn sanctum_spl_multi_matches_onchain() {
+    Case {
+        init: SvcAg::SanctumSplMulti(fixture_acc("jupsol-pool").pubkey),
+        prog: "sanctum-spl-multi-calc",
+        pool_prog: Some("sanctum-spl-multi"),
+        accs: &["jupsol-pool", "sanctum-spl-multi-calc-state"],
+        mint: "jupsol-mint",
+    }
+    .assert_matches_onchain();
+}
+
+#[test]
+fn wsol_matches_onchain() {
+    Case {
+        init: SvcAg::Wsol(()),
+        prog: "wsol-calc",
+        pool_prog: None,
+        accs: &[],
+        mint: "wsol-mint",
+    }
+    .assert_matches_onchain();
+}
+
+#[test]
+fn spl_no_cpi_matches_offchain() {
+    let jupsol_pool = fixture_acc("jupsol-pool");
+    let mint = fixture_acc("jupsol-mint").pubkey;
+    let epoch = StakePool::borsh_de(jupsol_pool.data.as_slice())
+        .unwrap()
+        .last_update_epoch;
+    [
+        (SvcAg::Spl(jupsol_pool.pubkey), spl::POOL_PROG_ID),
+        (
+            SvcAg::SanctumSpl(jupsol_pool.pubkey),
+            sanctum_spl::POOL_PROG_ID,
+        ),
+    ]
+    .into_iter()
+    .for_each(|(init, owner)| {
+        let pool = JsonAccount {
+            owner,
+            ..jupsol_pool.clone()
+        };
+        let mut fixtures: OwnedUpdateMap = [pool.clone().into_keyed_owned()].into_iter().collect();
+        fixtures.insert(
+            SYSVAR_CLOCK.to_bytes(),
+            OwnedAccount {
+                data: clock_data(epoch),
+                ..Default::default()
+            },
+        );
+        let mut svc = SvcAgStd::new(init);
+        svc.update_svc(&fixtures).unwrap();
+        let calc: SvcCalcAg = svc.as_sol_val_calc().unwrap().to_owned_copy();
+
+        let pool_state = |owner| PoolStateAcc {
+            key: &pool.pubkey,
+            owner,
+            data: &pool.data,
+        };
+        let [no_cpi_pool_state, wrong_owner_pool_state] =
+            [&owner, &sanctum_spl_multi::POOL_PROG_ID].map(|owner| match init {
+                SvcAg::Spl(_) => SvcAg::Spl(pool_state(owner)),
+                _ => SvcAg::SanctumSpl(pool_state(owner)),
+            });
+        assert_eq!(
+            svc_calc_ag_of_accs(&mint, &wrong_owner_pool_state, epoch),
+            Err(NoCpiErr::WrongPoolStateOwner)
+        );
+        let no_cpi_calc = svc_calc_ag_of_accs(&mint, &no_cpi_pool_state, epoch).unwrap();
+        assert_eq!(no_cpi_calc, calc, "{init:?} no_cpi");
+
+        AMTS.into_iter().for_each(|amt| {
+            assert_eq!(
+                no_cpi_calc.lst_to_sol(amt),
+                calc.lst_to_sol(amt),
+                "{init:?} no_cpi LstToSol {amt}"
+            );
+            assert_eq!(
+                no_cpi_calc.sol_to_lst(amt),
+                calc.sol_to_lst(amt),
+                "{init:?} no_cpi SolToLst {amt}"
+            );
+        });
+    });
+}
+
+impl Case {
+    fn assert_matches_onchain(self) {
+        let Self {
+            init,
+            prog,
+            pool_prog,
+            accs,
+            mint,
+        } = self;
+
+        let mut svc = SvcAgStd::new(init);
+        let prog_id = Pubkey::new_from_array(*svc.0.svc_program_id());
+        let mut mollusk = Mollusk::default();
+        mollusk.add_program_with_elf_and_loader(
+            &prog_id,
+            &read_program(&format!("{prog}.so")),
+            &LOADER_V3,
+        );
+
+        let mint = fixture_acc(mint);
+        let mint_addr = mint.pubkey;
+        let fixture_accs: Vec<JsonAccount> = accs
+            .iter()
+            .copied()
+            .map(fixture_acc)
+            .chain([mint])
+            .collect();
+        let mut accounts: Vec<(Pubkey, Account)> = fixture_accs.iter().map(solana_acc).collect();
+
+        // first fixture account is always the stake pool state account, if any
+        let pool_state = fixture_accs.first().map(|a| PoolStateAcc {
+            key: &a.pubkey,
+            owner: &a.owner,
+            data: &a.data,
+        });
+        let no_cpi_pool_state = match init {
+            SvcAg::Lido(_) => SvcAg::Lido(pool_state.unwrap()),
+            SvcAg::Marinade(_) => SvcAg::Marinade(pool_state.unwrap()),
+            SvcAg::SanctumSpl(_) => SvcAg::SanctumSpl(pool_state.unwrap()),
+            SvcAg::SanctumSplMulti(_) => SvcAg::SanctumSplMulti(pool_state.unwrap()),
+            SvcAg::Spl(_) => SvcAg::Spl(pool_state.unwrap()),
+            SvcAg::Wsol(_) => SvcAg::Wsol(()),
+        };
+
+        let suf_keys = svc.as_sol_val_calc_accs().suf_keys_owned();
+        if let (
+            Some(pool_prog),
+            SvcAg::Lido(k)
+            | SvcAg::Marinade(k)
+            | SvcAg::SanctumSpl(k)
+            | SvcAg::SanctumSplMulti(k)
+            | SvcAg::Spl(k),
+        ) = (pool_prog, &suf_keys)
+        {
+            accounts.extend(upgradeable_program_accs(
+                &mollusk,
+                Pubkey::new_from_array(*k.pool_prog()),
+                Pubkey::new_from_array(*k.pool_progdata()),
+                &read_program(&format!("stake-pools/{pool_prog}.so")),
+            ));
+        }
+
+        // first update with a dummy clock to find out the epoch the pool was last updated,
+        // then quote as if it is currently that epoch
+        let mut fixtures: OwnedUpdateMap = fixture_accs
+            .iter()
+            .cloned()
+            .map(JsonAccount::into_keyed_owned)
+            .collect();
+        fixtures.insert(
+            SYSVAR_CLOCK.to_bytes(),
+            OwnedAccount {
+                data: clock_data(0),
+                ..Default::default()
+            },
+        );
+        svc.update_svc(&fixtures).unwrap();
+        let mut calc: SvcCalcAg = svc.as_sol_val_calc().unwrap().to_owned_copy();
+        let epoch = match &mut calc {
+            SvcAg::Lido(c) => {
+                c.current_epoch = c.exchange_rate.computed_in_epoch;
+                c.current_epoch
+            }
+            SvcAg::SanctumSpl(c) | SvcAg::SanctumSplMulti(c) | SvcAg::Spl(c) => {
+                c.current_epoch = c.last_update_epoch;
+                c.current_epoch
+            }
+            SvcAg::Marinade(_) | SvcAg::Wsol(_) => 0,
+        };
+        mollusk.sysvars.clock.epoch = epoch;
+
+        let no_cpi_calc = svc_calc_ag_of_accs(&mint_addr, &no_cpi_pool_state, epoch).unwrap();
+        assert_eq!(no_cpi_calc, calc, "{prog} no_cpi");
+
+        AMTS.into_iter().for_each(|amt| {
+            [
+                (
+                    SvcIxTy::LstToSol,
+                    calc.lst_to_sol(amt),
+                    no_cpi_calc.lst_to_sol(amt),
+                ),
+                (
+                    SvcIxTy::SolToLst,
+                    calc.sol_to_lst(amt),
+                    no_cpi_calc.sol_to_lst(amt),
+                ),
+            ]
+            .into_iter()
+            .for_each(|(ty, expected, no_cpi)| {
+                let ix = svc.svc_ix(ty, amt).unwrap();
+                let onchain = invoke(&mollusk, &ix.into(), &accounts);
+                assert_eq!(no_cpi.ok(), onchain, "{prog} no_cpi {ty:?} {amt}");
+                match expected {
+                    Ok(r) => assert_eq!(onchain, Some(r), "{prog} {ty:?} {amt}"),
+                    Err(e) => assert_eq!(onchain, None, "{prog} {ty:?} {amt}: {e}"),
+                }
+            });
+        });
+    }
+}
+
+/// Returns `None` if the instruction failed
+fn invoke(
+    mollusk: &Mollusk,
+    ix: &Instruction,
+    accounts: &[(Pubkey, Account)],
+) -> Option<core::ops::RangeInclusive<u64>> {
+    let res = mollusk.process_instruction(ix, accounts);
+    if !res.program_result.is_ok() {
+        return None;
+    }
+    Some(decode_return_data(&res.return_data).unwrap())
+}
+
+/// Program and program data accounts for an upgradeable program
+/// that was last deployed/upgraded at slot 0,
+/// to match the `last_upgrade_slot` of the calculator state fixtures
+fn upgradeable_program_accs(
+    mollusk: &Mollusk,
+    prog: Pubkey,
+    progdata: Pubkey,
+    elf: &[u8],
+) -> [(Pubkey, Account); 2] {
+    // bincode-serialized UpgradeableLoaderState::Program
+    let prog_data: Vec<u8> = 2u32
+        .to_le_bytes()
+        .into_iter()
+        .chain(progdata.to_bytes())
+        .collect();
+    // ProgramData header followed by the ELF
+    let progdata_data: Vec<u8> = progdata_header(0)
+        .into_iter()
+        .chain(elf.iter().copied())
+        .collect();
+    [(prog, prog_data, true), (progdata, progdata_data, false)].map(|(pk, data, executable)| {
+        (
+            pk,
+            Account {
+                lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+                data,
+                owner: LOADER_V3,
+                executable,
+                rent_epoch: u64::MAX,
+            },
+        )
+    })
+}
+
+fn solana_acc(
+    JsonAccount {
+        pubkey,
+        lamports,
+        data,
+        owner,
+        executable,
+        rent_epoch,
+    }: &JsonAccount,
+) -> (Pubkey, Account) {
+    (
+        Pubkey::new_from_array(*pubkey),
+        Account {
+            lamports: *lamports,
+            data: data.clone(),
+            owner: Pubkey::new_from_array(*owner),
+            executable: *executable,
+            rent_epoch: *rent_epoch,
+        },
+    )
+}
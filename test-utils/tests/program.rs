@@ -0,0 +1,134 @@
+//! [`process_svc_ix`] tests against `sanctum-svc-jiminy-test-program`,
+//! a program built on [`SvcProgram`], executed in an in-process SVM.
+//!
+//! Build the program first with
+//! `cargo-build-sbf --manifest-path test-programs/jiminy/Cargo.toml`
+//!
+//! [`process_svc_ix`]: sanctum_svc_jiminy::program::process_svc_ix
+//! [`SvcProgram`]: sanctum_svc_jiminy::program::SvcProgram
+
+#![cfg(feature = "test-sbf")]
+
+use mollusk_svm::{program::loader_keys::LOADER_V3, result::InstructionResult, Mollusk};
+use sanctum_svc_jiminy::{
+    instructions::{
+        return_data::encode_return_data,
+        svc_ix::{SvcIx, SvcIxTy},
+    },
+    program::SvcProgramErr,
+    sanctum_svc_ag_core::sanctum_svc_wsol_core::LST_MINT,
+};
+use sanctum_svc_jiminy_test_program::TestCalcAccs;
+use sanctum_svc_test_utils::{fixture_acc, fixtures_dir};
+use solana_account::Account;
+use solana_instruction::{error::InstructionError, Instruction};
+use solana_pubkey::Pubkey;
+
+const AMTS: [u64; 4] = [0, 1, 1_000_000_000, u64::MAX];
+
+const PROG_ID: Pubkey = Pubkey::new_from_array([2; 32]);
+
+const UNKNOWN: Pubkey = Pubkey::new_from_array([3; 32]);
+
+#[test]
+fn quotes_wsol() {
+    let mollusk = mollusk();
+    AMTS.into_iter().for_each(|amt| {
+        [SvcIxTy::LstToSol, SvcIxTy::SolToLst]
+            .into_iter()
+            .for_each(|ty| {
+                let res = invoke(&mollusk, &svc_ix(ty, amt));
+                assert_eq!(res.raw_result, Ok(()), "{ty:?} {amt}");
+                assert_eq!(
+                    res.return_data,
+                    encode_return_data(&(amt..=amt)).unwrap(),
+                    "{ty:?} {amt}"
+                );
+            });
+    });
+}
+
+#[test]
+fn wrong_suf_acc() {
+    let mollusk = mollusk();
+    let mut ix = svc_ix(SvcIxTy::LstToSol, 1);
+    ix.accounts[1].pubkey = UNKNOWN;
+    assert_eq!(
+        invoke(&mollusk, &ix).raw_result,
+        Err(InstructionError::Custom(SvcProgramErr::WrongSufAcc.code()))
+    );
+}
+
+#[test]
+fn missing_suf_acc() {
+    let mollusk = mollusk();
+    let mut ix = svc_ix(SvcIxTy::LstToSol, 1);
+    ix.accounts.pop();
+    assert_eq!(
+        invoke(&mollusk, &ix).raw_result,
+        Err(InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[test]
+fn bad_ix_data() {
+    let mollusk = mollusk();
+    let mut ix = svc_ix(SvcIxTy::LstToSol, 1);
+    ix.data.push(0);
+    assert_eq!(
+        invoke(&mollusk, &ix).raw_result,
+        Err(InstructionError::InvalidInstructionData)
+    );
+}
+
+#[test]
+fn unsupported_mint() {
+    let mollusk = mollusk();
+    let mut ix = svc_ix(SvcIxTy::LstToSol, 1);
+    ix.accounts[0].pubkey = UNKNOWN;
+    assert_eq!(
+        invoke(&mollusk, &ix).raw_result,
+        Err(InstructionError::InvalidArgument)
+    );
+}
+
+fn mollusk() -> Mollusk {
+    let elf = std::fs::read(
+        fixtures_dir()
+            .parent()
+            .unwrap()
+            .join("target/deploy/sanctum_svc_jiminy_test_program.so"),
+    )
+    .expect("test program not built, run cargo-build-sbf");
+    let mut mollusk = Mollusk::default();
+    mollusk.add_program_with_elf_and_loader(&PROG_ID, &elf, &LOADER_V3);
+    mollusk
+}
+
+fn svc_ix(ty: SvcIxTy, amt: u64) -> Instruction {
+    SvcIx::new(PROG_ID.to_bytes(), LST_MINT, &TestCalcAccs, ty, amt).into()
+}
+
+/// Provides the wSOL mint fixture and an empty account for every other key
+fn invoke(mollusk: &Mollusk, ix: &Instruction) -> InstructionResult {
+    let mint = fixture_acc("wsol-mint");
+    let accounts: Vec<(Pubkey, Account)> = ix
+        .accounts
+        .iter()
+        .map(|m| {
+            let acc = if m.pubkey.to_bytes() == mint.pubkey {
+                Account {
+                    lamports: mint.lamports,
+                    data: mint.data.clone(),
+                    owner: Pubkey::new_from_array(mint.owner),
+                    executable: mint.executable,
+                    rent_epoch: mint.rent_epoch,
+                }
+            } else {
+                Account::default()
+            };
+            (m.pubkey, acc)
+        })
+        .collect();
+    mollusk.process_instruction(ix, &accounts)
+}
@@ -0,0 +1,59 @@
+//! [`resolve_init_data`] against the stake pool fixtures in `test-fixtures/`
+
+use sanctum_svc_ag_std::{
+    resolve::{resolve_init_data, ResolveErr},
+    update::{OwnedAccount, OwnedUpdateMap},
+    SvcAg,
+};
+use sanctum_svc_test_utils::fixture_acc;
+
+const UNKNOWN_OWNER_POOL: [u8; 32] = [1; 32];
+
+const UNKNOWN_OWNER: [u8; 32] = [2; 32];
+
+#[test]
+fn skips_unknown_owner_candidate() {
+    let (pool, map) = jupsol_map();
+    let mint = fixture_acc("jupsol-mint").pubkey;
+    assert_eq!(
+        resolve_init_data(&mint, [UNKNOWN_OWNER_POOL, pool], &map),
+        Ok(SvcAg::SanctumSplMulti(pool))
+    );
+}
+
+#[test]
+fn unknown_owner_if_no_other_match() {
+    let (_pool, map) = jupsol_map();
+    let mint = fixture_acc("jupsol-mint").pubkey;
+    assert_eq!(
+        resolve_init_data(&mint, [UNKNOWN_OWNER_POOL], &map),
+        Err(ResolveErr::UnknownPoolOwner {
+            pool: UNKNOWN_OWNER_POOL
+        })
+    );
+}
+
+#[test]
+fn not_found() {
+    let (pool, map) = jupsol_map();
+    assert_eq!(
+        resolve_init_data(&[3; 32], [UNKNOWN_OWNER_POOL, pool], &map),
+        Err(ResolveErr::NotFound)
+    );
+}
+
+/// Returns `(jupsol pool addr, map)`, where the map also contains
+/// a copy of the jupsol pool at [`UNKNOWN_OWNER_POOL`] owned by [`UNKNOWN_OWNER`]
+fn jupsol_map() -> ([u8; 32], OwnedUpdateMap) {
+    let (pool, acc) = fixture_acc("jupsol-pool").into_keyed_owned();
+    let unknown = OwnedAccount {
+        owner: UNKNOWN_OWNER,
+        ..acc.clone()
+    };
+    (
+        pool,
+        [(pool, acc), (UNKNOWN_OWNER_POOL, unknown)]
+            .into_iter()
+            .collect(),
+    )
+}
@@ -0,0 +1,96 @@
+//! Stake pool program upgrade detection of the per-LST std calculators,
+//! using the calculator state fixtures in `test-fixtures/`,
+//! which record a `last_upgrade_slot` of 0.
+
+use sanctum_svc_ag_std::{
+    sanctum_svc_generic::upgrade::{LastUpgradeSlots, PoolProgUpgradedErr},
+    sanctum_svc_lido_std::{
+        instructions::sol_val_calc::IX_SUF_KEYS_OWNED as LIDO_SUF_KEYS, LidoSvcStd,
+    },
+    sanctum_svc_marinade_std::{
+        instructions::sol_val_calc::IX_SUF_KEYS_OWNED as MARINADE_SUF_KEYS, MarinadeSvcStd,
+    },
+    update::{OwnedAccount, OwnedUpdateMap, UpdateSvc},
+};
+use sanctum_svc_test_utils::{clock_data, fixture_acc, progdata_header, SYSVAR_CLOCK};
+
+#[test]
+fn lido_not_upgraded() {
+    let mut svc = LidoSvcStd::DEFAULT.with_upgrade_check();
+    svc.update_svc(&update_map(
+        &["stsol-pool", "lido-calc-state"],
+        LIDO_SUF_KEYS.pool_progdata(),
+        0,
+    ))
+    .unwrap();
+    assert!(svc.try_as_calc().unwrap().is_some());
+}
+
+#[test]
+fn lido_upgraded() {
+    let mut svc = LidoSvcStd::DEFAULT.with_upgrade_check();
+    svc.update_svc(&update_map(
+        &["stsol-pool", "lido-calc-state"],
+        LIDO_SUF_KEYS.pool_progdata(),
+        1,
+    ))
+    .unwrap();
+    assert_eq!(svc.try_as_calc(), Err(upgraded_err(1)));
+    // unchecked accessor still returns the calc
+    assert!(svc.as_calc().is_some());
+}
+
+#[test]
+fn marinade_upgraded() {
+    let mut svc = MarinadeSvcStd::DEFAULT.with_upgrade_check();
+    svc.update_svc(&update_map(
+        &["msol-pool", "marinade-calc-state"],
+        MARINADE_SUF_KEYS.pool_progdata(),
+        1,
+    ))
+    .unwrap();
+    assert_eq!(svc.try_as_calc(), Err(upgraded_err(1)));
+}
+
+#[test]
+fn upgrade_check_disabled() {
+    let mut svc = LidoSvcStd::DEFAULT;
+    svc.update_svc(&update_map(
+        &["stsol-pool"],
+        LIDO_SUF_KEYS.pool_progdata(),
+        1,
+    ))
+    .unwrap();
+    assert!(svc.try_as_calc().unwrap().is_some());
+}
+
+fn upgraded_err(pool_progdata: u64) -> PoolProgUpgradedErr {
+    PoolProgUpgradedErr(LastUpgradeSlots {
+        state: 0,
+        pool_progdata,
+    })
+}
+
+/// Fixture accounts, a clock at epoch 0,
+/// and stake pool program data last upgraded at `progdata_slot`
+fn update_map(fixtures: &[&str], pool_progdata: &[u8; 32], progdata_slot: u64) -> OwnedUpdateMap {
+    let mut map: OwnedUpdateMap = fixtures
+        .iter()
+        .map(|name| fixture_acc(name).into_keyed_owned())
+        .collect();
+    map.insert(
+        SYSVAR_CLOCK.to_bytes(),
+        OwnedAccount {
+            data: clock_data(0),
+            ..Default::default()
+        },
+    );
+    map.insert(
+        *pool_progdata,
+        OwnedAccount {
+            data: progdata_header(progdata_slot),
+            ..Default::default()
+        },
+    );
+    map
+}
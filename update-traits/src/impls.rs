@@ -0,0 +1,102 @@
+//! Impls for foreign types, each behind their own feature
+
+#[cfg(feature = "std")]
+mod hash_map {
+    use std::collections::HashMap;
+
+    use crate::{Account, UpdateMap};
+
+    impl<A: Account> UpdateMap for HashMap<[u8; 32], A> {
+        type Account<'a>
+            = &'a A
+        where
+            Self: 'a;
+
+        #[inline]
+        fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+            self.get(pk)
+        }
+    }
+}
+
+#[cfg(feature = "solana-account")]
+mod sdk_account {
+    use solana_account::{AccountSharedData, ReadableAccount};
+
+    use crate::{Account, AccountOwner};
+
+    impl Account for solana_account::Account {
+        #[inline]
+        fn data(&self) -> &[u8] {
+            &self.data
+        }
+    }
+
+    impl AccountOwner for solana_account::Account {
+        #[inline]
+        fn owner(&self) -> [u8; 32] {
+            self.owner.to_bytes()
+        }
+    }
+
+    impl Account for AccountSharedData {
+        #[inline]
+        fn data(&self) -> &[u8] {
+            ReadableAccount::data(self)
+        }
+    }
+
+    impl AccountOwner for AccountSharedData {
+        #[inline]
+        fn owner(&self) -> [u8; 32] {
+            ReadableAccount::owner(self).to_bytes()
+        }
+    }
+}
+
+#[cfg(feature = "solana-pubkey")]
+mod sdk_pubkey {
+    use std::collections::HashMap;
+
+    use solana_pubkey::Pubkey;
+
+    use crate::{Account, UpdateMap};
+
+    impl<A: Account> UpdateMap for HashMap<Pubkey, A> {
+        type Account<'a>
+            = &'a A
+        where
+            Self: 'a;
+
+        #[inline]
+        fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+            self.get(&Pubkey::new_from_array(*pk))
+        }
+    }
+}
+
+#[cfg(feature = "solana-account-decoder")]
+mod ui_account {
+    use std::collections::HashMap;
+
+    use solana_account_decoder_client_types::UiAccount;
+    use solana_pubkey::Pubkey;
+
+    use crate::UpdateMap;
+
+    /// Account data is decoded on every [`UpdateMap::get_account`] call.
+    /// Accounts whose data cannot be decoded, e.g. `jsonParsed` encoding,
+    /// are treated as missing.
+    impl UpdateMap for HashMap<Pubkey, UiAccount> {
+        type Account<'a>
+            = solana_account::Account
+        where
+            Self: 'a;
+
+        #[inline]
+        fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+            self.get(&Pubkey::new_from_array(*pk))
+                .and_then(UiAccount::decode)
+        }
+    }
+}
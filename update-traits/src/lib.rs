@@ -1,7 +1,15 @@
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::{error::Error, fmt::Display};
 
+mod impls;
+
+#[cfg(feature = "std")]
+pub mod owned;
+
 pub trait Account {
     fn data(&self) -> &[u8];
 }
@@ -15,6 +23,19 @@ impl<T: Account> Account for &T {
     }
 }
 
+/// An [`Account`] that also knows the program that owns it
+pub trait AccountOwner: Account {
+    fn owner(&self) -> [u8; 32];
+}
+
+/// Blanket for refs
+impl<T: AccountOwner> AccountOwner for &T {
+    #[inline]
+    fn owner(&self) -> [u8; 32] {
+        (*self).owner()
+    }
+}
+
 pub trait UpdateMap {
     type Account<'a>: Account
     where
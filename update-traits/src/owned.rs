@@ -0,0 +1,84 @@
+//! In-memory owned [`Account`] and [`UpdateMap`] implementations
+
+use std::{collections::HashMap, vec::Vec};
+
+use crate::{Account, AccountOwner, UpdateMap};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedAccount {
+    pub data: Vec<u8>,
+    pub owner: [u8; 32],
+    pub lamports: u64,
+
+    /// Slot at which the account was fetched
+    pub slot: u64,
+}
+
+impl Account for OwnedAccount {
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AccountOwner for OwnedAccount {
+    #[inline]
+    fn owner(&self) -> [u8; 32] {
+        self.owner
+    }
+}
+
+/// Account store keyed by pubkey
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct OwnedUpdateMap(pub HashMap<[u8; 32], OwnedAccount>);
+
+/// Constructors
+impl OwnedUpdateMap {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Mutators
+impl OwnedUpdateMap {
+    /// Returns the replaced account, if any
+    #[inline]
+    pub fn insert(&mut self, pk: [u8; 32], acc: OwnedAccount) -> Option<OwnedAccount> {
+        self.0.insert(pk, acc)
+    }
+}
+
+impl FromIterator<([u8; 32], OwnedAccount)> for OwnedUpdateMap {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = ([u8; 32], OwnedAccount)>>(iter: T) -> Self {
+        Self(HashMap::from_iter(iter))
+    }
+}
+
+impl Extend<([u8; 32], OwnedAccount)> for OwnedUpdateMap {
+    #[inline]
+    fn extend<T: IntoIterator<Item = ([u8; 32], OwnedAccount)>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for OwnedUpdateMap {
+    type Item = ([u8; 32], OwnedAccount);
+    type IntoIter = std::collections::hash_map::IntoIter<[u8; 32], OwnedAccount>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl UpdateMap for OwnedUpdateMap {
+    type Account<'a> = &'a OwnedAccount;
+
+    #[inline]
+    fn get_account(&self, pk: &[u8; 32]) -> Option<Self::Account<'_>> {
+        self.0.get(pk)
+    }
+}
@@ -1,6 +1,9 @@
 use core::{convert::Infallible, ops::RangeInclusive};
 
-use sanctum_svc_core::traits::SolValCalc;
+use sanctum_svc_core::{
+    sanctum_u64_ratio::Ratio,
+    traits::{LstToSolRatios, SolValCalc, SolValCalcRatios},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WsolCalc;
@@ -17,6 +20,23 @@ impl WsolCalc {
     }
 }
 
+/// SolValCalcRatios
+impl WsolCalc {
+    #[inline]
+    pub const fn svc_lst_to_sol_ratios(&self) -> LstToSolRatios {
+        LstToSolRatios::no_fee(Ratio { n: 1, d: 1 })
+    }
+}
+
+impl SolValCalcRatios for WsolCalc {
+    type Error = Infallible;
+
+    #[inline]
+    fn lst_to_sol_ratios(&self) -> Result<LstToSolRatios, Self::Error> {
+        Ok(self.svc_lst_to_sol_ratios())
+    }
+}
+
 impl SolValCalc for WsolCalc {
     type Error = Infallible;
 
@@ -31,6 +31,12 @@ impl<T> AsRef<[T]> for IxSufAccs<T> {
 pub struct WsolCalcAccs;
 
 impl WsolCalcAccs {
+    /// The calculator program's program ID
+    #[inline]
+    pub const fn svc_program_id(&self) -> &[u8; 32] {
+        &crate::ID
+    }
+
     #[inline]
     pub const fn svc_suf_keys_owned(&self) -> IxSufKeysOwned {
         IxSufAccs::new()
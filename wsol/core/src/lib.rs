@@ -8,3 +8,7 @@ pub mod instructions;
 
 pub const ID_STR: &str = "wsoGmxQLSvwWpuaidCApxN5kEowLe2HLQLJhCQnj4bE";
 pub const ID: [u8; 32] = const_crypto::bs58::decode_pubkey(ID_STR);
+
+/// The native mint
+pub const LST_MINT_STR: &str = "So11111111111111111111111111111111111111112";
+pub const LST_MINT: [u8; 32] = const_crypto::bs58::decode_pubkey(LST_MINT_STR);
@@ -1,4 +1,9 @@
-use sanctum_svc_wsol_core::{calc::WsolCalc, instructions::sol_val_calc::WsolCalcAccs};
+use sanctum_svc_wsol_core::{
+    calc::WsolCalc,
+    instructions::sol_val_calc::{IxSufAccFlags, IxSufKeysOwned, WsolCalcAccs},
+    sanctum_svc_core::instructions::svc_ix::{SvcIx, SvcIxTy},
+    LST_MINT,
+};
 
 // Re-exports
 pub use sanctum_svc_wsol_core::*;
@@ -19,4 +24,23 @@ impl WsolSvcStd {
     pub const fn as_accs(&self) -> &WsolCalcAccs {
         &WsolCalcAccs
     }
+
+    /// The native mint
+    #[inline]
+    pub const fn lst_mint(&self) -> &[u8; 32] {
+        &LST_MINT
+    }
+}
+
+/// Instructions
+impl WsolSvcStd {
+    /// Complete SolValCalc interface instruction for [`Self::lst_mint`].
+    ///
+    /// `amt` is LST amount for [`SvcIxTy::LstToSol`]
+    /// and lamport amount for [`SvcIxTy::SolToLst`].
+    #[inline]
+    pub fn svc_ix(&self, ty: SvcIxTy, amt: u64) -> SvcIx<IxSufKeysOwned, IxSufAccFlags> {
+        let accs = self.as_accs();
+        SvcIx::new(*accs.svc_program_id(), *self.lst_mint(), accs, ty, amt)
+    }
 }